@@ -0,0 +1,66 @@
+use clap::Parser;
+use rusty_tesseract::{ServerOptions, Tesseract};
+use std::{net::SocketAddr, path::PathBuf, process::ExitCode};
+
+/// Serves OCR over HTTP with JSON responses.
+#[derive(Debug, Parser)]
+#[command(name = "rusty-tesseract-server", version)]
+struct Cli {
+    /// Address to listen on.
+    #[arg(long, default_value = "127.0.0.1:8080")]
+    addr: SocketAddr,
+
+    /// Tesseract executable, defaults to TESSERACT_CMD or `tesseract` on PATH.
+    #[arg(long, value_name = "PATH")]
+    tesseract: Option<PathBuf>,
+
+    /// Tesseract processes that may run at the same time, defaults to the
+    /// number of CPUs.
+    #[arg(long, value_name = "N", default_value_t = ServerOptions::default().max_processes)]
+    max_processes: usize,
+
+    /// Largest accepted request body in bytes.
+    #[arg(long, value_name = "BYTES", default_value_t = ServerOptions::default().max_request_size)]
+    max_request_size: usize,
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    let cli = Cli::parse();
+    let tesseract = cli
+        .tesseract
+        .map_or_else(Tesseract::default, Tesseract::new);
+    let router = tesseract.server_router(ServerOptions {
+        max_processes: cli.max_processes,
+        max_request_size: cli.max_request_size,
+        ..Default::default()
+    });
+
+    let listener = match tokio::net::TcpListener::bind(cli.addr).await {
+        Ok(x) => x,
+        Err(error) => {
+            eprintln!(
+                "rusty-tesseract-server: could not listen on {}: {}",
+                cli.addr, error
+            );
+            // EX_UNAVAILABLE
+            return ExitCode::from(69);
+        }
+    };
+    eprintln!("rusty-tesseract-server: listening on http://{}", cli.addr);
+
+    let shutdown = async {
+        let _ = tokio::signal::ctrl_c().await;
+    };
+    match axum::serve(listener, router)
+        .with_graceful_shutdown(shutdown)
+        .await
+    {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(error) => {
+            eprintln!("rusty-tesseract-server: {}", error);
+            // EX_IOERR
+            ExitCode::from(74)
+        }
+    }
+}
@@ -0,0 +1,454 @@
+use clap::{Parser, Subcommand};
+use rusty_tesseract::{
+    Args, CancellationToken, ConfigValue, Image, OcrEngineMode, PageSegMode, SidecarFormat,
+    TessError, TessResult, Tesseract, WatchOptions,
+};
+use std::{
+    fmt, io,
+    path::{Path, PathBuf},
+    process::ExitCode,
+    time::Duration,
+};
+
+mod output;
+mod rpc;
+use output::*;
+
+#[cfg(all(test, unix))]
+#[path = "tesseract/test_util/fake_tesseract.rs"]
+mod fake_tesseract;
+
+/// Runs tesseract on images and prints the results as text, JSON or CSV.
+#[derive(Debug, Parser)]
+#[command(name = "rusty-tesseract", version)]
+pub struct Cli {
+    /// Tesseract executable, defaults to TESSERACT_CMD or `tesseract` on PATH.
+    #[arg(long, global = true, value_name = "PATH")]
+    tesseract: Option<PathBuf>,
+
+    #[arg(short, long, global = true, value_enum, default_value_t = Format::Text)]
+    format: Format,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// Recognized text.
+    String(ImageOptions),
+    /// Box file with one line per symbol.
+    Boxes(ImageOptions),
+    /// TSV data with one row per page, block, paragraph, line and word.
+    Data(ImageOptions),
+    /// hOCR document.
+    Hocr(ImageOptions),
+    /// Orientation and script detection.
+    Osd(ImageOptions),
+    /// Version of the tesseract executable.
+    Version,
+    /// Installed languages.
+    Langs,
+    /// Config parameters with their defaults.
+    Params {
+        /// Only list parameters whose name or description contains QUERY.
+        query: Option<String>,
+    },
+    /// Recognize images added to a directory and write sidecar files next
+    /// to them, until interrupted.
+    Watch(WatchCommand),
+    /// Answer JSON-RPC 2.0 requests, one per line on stdin, until stdin is
+    /// closed.
+    Rpc {
+        /// Requests that may run at the same time, defaults to the number of
+        /// CPUs.
+        #[arg(long, value_name = "N", default_value_t = default_jobs())]
+        jobs: usize,
+    },
+}
+
+#[derive(Debug, clap::Args)]
+struct ImageOptions {
+    /// Image paths or glob patterns such as `scans/*.png`.
+    #[arg(required = true, value_name = "IMAGE")]
+    images: Vec<String>,
+
+    #[command(flatten)]
+    args: ArgsOptions,
+}
+
+#[derive(Debug, clap::Args)]
+struct WatchCommand {
+    dir: PathBuf,
+
+    /// Sidecar files to write: txt, tsv or json. May be repeated or comma
+    /// separated.
+    #[arg(
+        long = "sidecar",
+        value_name = "FORMAT",
+        value_delimiter = ',',
+        default_value = "txt"
+    )]
+    sidecars: Vec<SidecarFormat>,
+
+    /// Seconds between two scans of the directory.
+    #[arg(long, value_name = "SECONDS", value_parser = parse_seconds, default_value = "1")]
+    interval: Duration,
+
+    /// Seconds a file must stay unchanged before it is recognized.
+    #[arg(long, value_name = "SECONDS", value_parser = parse_seconds, default_value = "2")]
+    settle: Duration,
+
+    /// Record of processed files, defaults to a hidden file in DIR.
+    #[arg(long, value_name = "PATH")]
+    state_file: Option<PathBuf>,
+
+    #[command(flatten)]
+    args: ArgsOptions,
+}
+
+/// Flags for every configurable field of [`Args`].
+#[derive(Debug, clap::Args)]
+struct ArgsOptions {
+    /// Model languages, e.g. `eng` or `eng+deu`.
+    #[arg(short, long, default_value_t = Args::default().lang)]
+    lang: String,
+
+    /// Config variable passed to tesseract with `-c`, may be repeated.
+    #[arg(short = 'c', long = "config", value_name = "NAME=VALUE", value_parser = parse_config_variable)]
+    config_variables: Vec<(String, ConfigValue)>,
+
+    #[arg(long, default_value_t = Args::default().dpi)]
+    dpi: i32,
+
+    /// Page segmentation mode by number or name, e.g. `6` or `single_block`.
+    #[arg(long, default_value_t = Args::default().psm)]
+    psm: PageSegMode,
+
+    /// OCR engine mode by number or name, e.g. `1` or `lstm_only`.
+    #[arg(long, default_value_t = Args::default().oem)]
+    oem: OcrEngineMode,
+
+    /// Kill tesseract after this many seconds.
+    #[arg(long, value_name = "SECONDS", value_parser = parse_seconds)]
+    timeout: Option<Duration>,
+
+    /// Detect the orientation first and recognize an upright copy.
+    #[arg(long)]
+    auto_rotate: bool,
+}
+
+impl ArgsOptions {
+    fn to_args(&self) -> Args {
+        Args {
+            lang: self.lang.clone(),
+            config_variables: self.config_variables.iter().cloned().collect(),
+            dpi: self.dpi,
+            psm: self.psm,
+            oem: self.oem,
+            timeout: self.timeout,
+            cancellation: None,
+            auto_rotate: self.auto_rotate,
+        }
+    }
+}
+
+fn parse_config_variable(s: &str) -> Result<(String, ConfigValue), String> {
+    let (name, value) = s
+        .split_once('=')
+        .ok_or_else(|| format!("expected NAME=VALUE, got '{}'", s))?;
+    Ok((name.into(), ConfigValue::infer(value)))
+}
+
+fn parse_seconds(s: &str) -> Result<Duration, String> {
+    s.parse::<f64>()
+        .ok()
+        .and_then(|x| Duration::try_from_secs_f64(x).ok())
+        .ok_or_else(|| format!("expected a number of seconds, got '{}'", s))
+}
+
+/// Exit code for command-line usage errors (`EX_USAGE`).
+const USAGE_EXIT_CODE: u8 = 64;
+
+/// Exit code for a failed call, following the conventions of `sysexits.h`
+/// and `timeout(1)`.
+pub fn exit_code(error: &TessError) -> u8 {
+    match error {
+        TessError::ArgumentError(_) => USAGE_EXIT_CODE,
+        TessError::ImageFormatError => 65,
+        TessError::ImageNotFoundError | TessError::ImageReadError(_) => 66,
+        TessError::TesseractNotFoundError
+        | TessError::SpawnError(_)
+        | TessError::VersionError(_)
+        | TessError::UnsupportedVersionError { .. }
+        | TessError::MissingTraineddataError(_) => 69,
+        TessError::ExitCodeError { .. }
+        | TessError::SignalError { .. }
+        | TessError::ProcessIoError(_) => 70,
+        TessError::TempfileError(_) | TessError::DynamicImageError(_) => 73,
+        TessError::WatchError(_) => 74,
+        TessError::OutputEncodingError(_)
+        | TessError::ParseError(_)
+        | TessError::SchemaError(_)
+        | TessError::LineParseError { .. } => 76,
+        TessError::TimeoutError(_) => 124,
+        TessError::CancelledError => 130,
+    }
+}
+
+/// Failure of a command, either of a tesseract call or while writing the
+/// results.
+#[derive(Debug)]
+pub enum CliError {
+    Tesseract(TessError),
+    /// Some images failed; each error was reported with its image already.
+    Images(TessError),
+    Output(io::Error),
+}
+
+impl CliError {
+    pub fn exit_code(&self) -> u8 {
+        match self {
+            CliError::Tesseract(error) | CliError::Images(error) => exit_code(error),
+            // EX_IOERR
+            CliError::Output(_) => 74,
+        }
+    }
+}
+
+impl From<TessError> for CliError {
+    fn from(error: TessError) -> Self {
+        CliError::Tesseract(error)
+    }
+}
+
+impl From<io::Error> for CliError {
+    fn from(error: io::Error) -> Self {
+        CliError::Output(error)
+    }
+}
+
+impl From<csv::Error> for CliError {
+    fn from(error: csv::Error) -> Self {
+        CliError::Output(error.into())
+    }
+}
+
+impl fmt::Display for CliError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CliError::Tesseract(error) => write!(f, "{}", error),
+            CliError::Images(_) => write!(f, "Not all images could be recognized."),
+            CliError::Output(error) => write!(f, "Could not write the output.\n{}", error),
+        }
+    }
+}
+
+pub fn run(cli: Cli) -> ExitCode {
+    match execute(cli) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(error) => {
+            eprintln!("rusty-tesseract: {}", error);
+            ExitCode::from(error.exit_code())
+        }
+    }
+}
+
+fn execute(cli: Cli) -> Result<(), CliError> {
+    let tesseract = cli
+        .tesseract
+        .map_or_else(Tesseract::default, Tesseract::new);
+    let format = cli.format;
+
+    match cli.command {
+        Command::String(x) => run_images(&x, format, |image, args| {
+            tesseract.image_to_string(image, args)
+        }),
+        Command::Boxes(x) => run_images(&x, format, |image, args| {
+            tesseract.image_to_boxes(image, args)
+        }),
+        Command::Data(x) => run_images(&x, format, |image, args| {
+            tesseract.image_to_data(image, args)
+        }),
+        Command::Hocr(x) => run_images(&x, format, |image, args| {
+            tesseract.image_to_hocr(image, args)
+        }),
+        Command::Osd(x) => run_images(&x, format, |image, args| {
+            tesseract.image_to_osd(image, args)
+        }),
+        Command::Version => print_version(&tesseract.get_tesseract_version()?, format),
+        Command::Langs => print_langs(&tesseract.get_tesseract_langs()?, format),
+        Command::Params { query } => print_params(
+            &tesseract.get_tesseract_config_parameters()?,
+            query.as_deref(),
+            format,
+        ),
+        Command::Watch(x) => watch(&tesseract, x, format),
+        Command::Rpc { jobs } => rpc::serve(&tesseract, jobs, io::stdin().lock(), io::stdout()),
+    }
+}
+
+fn default_jobs() -> usize {
+    std::thread::available_parallelism().map_or(1, |x| x.get())
+}
+
+/// Runs until the process is interrupted. The record of processed files is
+/// saved after every image, so nothing is lost when it is killed.
+fn watch(tesseract: &Tesseract, command: WatchCommand, format: Format) -> Result<(), CliError> {
+    let options = WatchOptions {
+        args: command.args.to_args(),
+        sidecars: command.sidecars,
+        poll_interval: command.interval,
+        settle_time: command.settle,
+        state_file: command.state_file,
+    };
+    let mut watcher = tesseract.watch_directory(command.dir, options)?;
+
+    let mut printer = WatchPrinter::new(format)?;
+    let cancellation = CancellationToken::new();
+    let mut output_error = None;
+    watcher.run(&cancellation, |event| {
+        if let Err(error) = printer.print(&event) {
+            output_error.get_or_insert(error);
+            cancellation.cancel();
+        }
+    })?;
+    output_error.map_or(Ok(()), Err)
+}
+
+/// Recognizes every image, reporting failures on stderr as they happen and
+/// carrying on with the remaining images. Fails with the first error once all
+/// results are written.
+fn run_images<T: Report>(
+    options: &ImageOptions,
+    format: Format,
+    recognize: impl Fn(&Image, &Args) -> TessResult<T>,
+) -> Result<(), CliError> {
+    let args = options.args.to_args();
+    args.validate()?;
+    let paths = expand_images(&options.images)?;
+
+    let mut printer = Printer::new(format, paths.len() > 1)?;
+    let mut first_error = None;
+    for path in &paths {
+        let result = load_image(path).and_then(|image| recognize(&image, &args));
+        if let Err(error) = &result {
+            eprintln!("rusty-tesseract: {}: {}", path.display(), error);
+        }
+        printer.print(path, &result)?;
+        if let Err(error) = result {
+            first_error.get_or_insert(error);
+        }
+    }
+    printer.finish()?;
+
+    first_error.map_or(Ok(()), |x| Err(CliError::Images(x)))
+}
+
+fn load_image(path: &Path) -> TessResult<Image> {
+    if !path.is_file() {
+        return Err(TessError::ImageNotFoundError);
+    }
+    Image::from_path(path)
+}
+
+/// Expands glob patterns in their order on the command line. Arguments
+/// without glob characters are taken as they are.
+fn expand_images(patterns: &[String]) -> TessResult<Vec<PathBuf>> {
+    let mut paths = Vec::new();
+    for pattern in patterns {
+        if !pattern.contains(['*', '?', '[']) {
+            paths.push(PathBuf::from(pattern));
+            continue;
+        }
+        let matches = glob::glob(pattern)
+            .map_err(|e| TessError::ArgumentError(format!("invalid pattern '{}': {}", pattern, e)))?
+            .filter_map(Result::ok)
+            .filter(|x| x.is_file())
+            .collect::<Vec<_>>();
+        if matches.is_empty() {
+            return Err(TessError::ArgumentError(format!(
+                "no images match '{}'",
+                pattern
+            )));
+        }
+        paths.extend(matches);
+    }
+    Ok(paths)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::CommandFactory;
+
+    #[test]
+    fn test_cli_args() {
+        Cli::command().debug_assert();
+
+        let cli = Cli::try_parse_from([
+            "rusty-tesseract",
+            "data",
+            "img/string.png",
+            "--psm",
+            "single_block",
+            "-c",
+            "tessedit_char_whitelist=ABC",
+            "-c",
+            "textord_heavy_nr=1",
+            "--timeout",
+            "2.5",
+            "--format",
+            "csv",
+        ])
+        .unwrap();
+        assert_eq!(cli.format, Format::Csv);
+        let Command::Data(options) = cli.command else {
+            panic!("expected the data subcommand");
+        };
+        let args = options.args.to_args();
+        assert_eq!(args.psm, PageSegMode::SingleBlock);
+        assert_eq!(args.oem, Args::default().oem);
+        assert_eq!(
+            args.config_variables.get("textord_heavy_nr"),
+            Some(&ConfigValue::Int(1))
+        );
+        assert_eq!(args.timeout, Some(Duration::from_millis(2500)));
+
+        assert!(Cli::try_parse_from(["rusty-tesseract", "string"]).is_err());
+        assert!(Cli::try_parse_from(["rusty-tesseract", "string", "a.png", "-c", "x"]).is_err());
+        assert!(
+            Cli::try_parse_from(["rusty-tesseract", "string", "a.png", "--psm", "14"]).is_err()
+        );
+    }
+
+    #[test]
+    fn test_expand_images() {
+        let paths = expand_images(&["img/string.png".into(), "img/*_text.png".into()]).unwrap();
+        assert_eq!(
+            paths,
+            vec![
+                PathBuf::from("img/string.png"),
+                PathBuf::from("img/horizontal_text.png"),
+                PathBuf::from("img/vertical_text.png"),
+            ]
+        );
+        assert_eq!(
+            expand_images(&["img/*.nothing".into()]),
+            Err(TessError::ArgumentError(
+                "no images match 'img/*.nothing'".into()
+            ))
+        );
+    }
+
+    #[test]
+    fn test_exit_code() {
+        assert_eq!(exit_code(&TessError::ImageNotFoundError), 66);
+        assert_eq!(exit_code(&TessError::TesseractNotFoundError), 69);
+        assert_eq!(exit_code(&TessError::TempfileError("full".into())), 73);
+        assert_eq!(
+            exit_code(&TessError::TimeoutError(Duration::from_secs(1))),
+            124
+        );
+    }
+}
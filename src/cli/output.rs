@@ -0,0 +1,450 @@
+use super::CliError;
+use rusty_tesseract::{
+    BoxOutput, ConfigParameterOutput, DataOutput, HocrOutput, OsdOutput, StringOutput, TessResult,
+    TesseractVersion, WatchEvent,
+};
+use serde::Serialize;
+use serde_json::json;
+use std::{
+    io::{self, Stdout, Write},
+    marker::PhantomData,
+    path::Path,
+};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, clap::ValueEnum)]
+pub enum Format {
+    /// Output as tesseract prints it.
+    Text,
+    /// One JSON document for all inputs.
+    Json,
+    /// One CSV row per result with a header line.
+    Csv,
+}
+
+/// Output of an image command in each [`Format`].
+pub trait Report: Serialize {
+    /// Text as tesseract printed it.
+    fn text(&self) -> &str;
+
+    const CSV_HEADER: &'static [&'static str];
+
+    fn csv_records(&self) -> Vec<Vec<String>>;
+}
+
+impl Report for StringOutput {
+    fn text(&self) -> &str {
+        &self.output
+    }
+
+    const CSV_HEADER: &'static [&'static str] = &["text"];
+
+    fn csv_records(&self) -> Vec<Vec<String>> {
+        vec![vec![self.output.clone()]]
+    }
+}
+
+impl Report for BoxOutput {
+    fn text(&self) -> &str {
+        &self.output
+    }
+
+    const CSV_HEADER: &'static [&'static str] =
+        &["symbol", "left", "bottom", "right", "top", "page"];
+
+    fn csv_records(&self) -> Vec<Vec<String>> {
+        self.boxes
+            .iter()
+            .map(|x| {
+                vec![
+                    x.symbol.clone(),
+                    x.left.to_string(),
+                    x.bottom.to_string(),
+                    x.right.to_string(),
+                    x.top.to_string(),
+                    x.page.to_string(),
+                ]
+            })
+            .collect()
+    }
+}
+
+impl Report for DataOutput {
+    fn text(&self) -> &str {
+        &self.output
+    }
+
+    const CSV_HEADER: &'static [&'static str] = &[
+        "level",
+        "page_num",
+        "block_num",
+        "par_num",
+        "line_num",
+        "word_num",
+        "left",
+        "top",
+        "width",
+        "height",
+        "conf",
+        "text",
+    ];
+
+    fn csv_records(&self) -> Vec<Vec<String>> {
+        self.data
+            .iter()
+            .map(|x| {
+                vec![
+                    x.level.to_string(),
+                    x.page_num.to_string(),
+                    x.block_num.to_string(),
+                    x.par_num.to_string(),
+                    x.line_num.to_string(),
+                    x.word_num.to_string(),
+                    x.left.to_string(),
+                    x.top.to_string(),
+                    x.width.to_string(),
+                    x.height.to_string(),
+                    x.conf.to_string(),
+                    x.text.clone(),
+                ]
+            })
+            .collect()
+    }
+}
+
+/// Words with their bounding boxes, the hOCR document itself only fits the
+/// text and JSON formats.
+impl Report for HocrOutput {
+    fn text(&self) -> &str {
+        &self.output
+    }
+
+    const CSV_HEADER: &'static [&'static str] =
+        &["id", "left", "top", "right", "bottom", "confidence", "text"];
+
+    fn csv_records(&self) -> Vec<Vec<String>> {
+        self.words()
+            .map(|x| {
+                vec![
+                    x.id.clone(),
+                    x.bbox.left.to_string(),
+                    x.bbox.top.to_string(),
+                    x.bbox.right.to_string(),
+                    x.bbox.bottom.to_string(),
+                    x.confidence.map_or_else(String::new, |x| x.to_string()),
+                    x.text.clone(),
+                ]
+            })
+            .collect()
+    }
+}
+
+impl Report for OsdOutput {
+    fn text(&self) -> &str {
+        &self.output
+    }
+
+    const CSV_HEADER: &'static [&'static str] = &[
+        "page_number",
+        "orientation",
+        "rotate",
+        "orientation_confidence",
+        "script",
+        "script_confidence",
+    ];
+
+    fn csv_records(&self) -> Vec<Vec<String>> {
+        vec![vec![
+            self.page_number.to_string(),
+            self.orientation.to_string(),
+            self.rotate.to_string(),
+            self.orientation_confidence.to_string(),
+            self.script.clone(),
+            self.script_confidence.to_string(),
+        ]]
+    }
+}
+
+/// Writes the results of an image command, to stdout unless another writer
+/// is given. JSON is buffered until [`Printer::finish`] so that all images
+/// form one array.
+pub struct Printer<T, W = Stdout> {
+    format: Format,
+    /// Whether to name the image before its text output.
+    multiple: bool,
+    writer: W,
+    json: Vec<serde_json::Value>,
+    output: PhantomData<T>,
+}
+
+impl<T: Report> Printer<T> {
+    pub fn new(format: Format, multiple: bool) -> Result<Self, CliError> {
+        Self::with_writer(io::stdout(), format, multiple)
+    }
+}
+
+impl<T: Report, W: Write> Printer<T, W> {
+    pub fn with_writer(mut writer: W, format: Format, multiple: bool) -> Result<Self, CliError> {
+        if format == Format::Csv {
+            let mut csv = csv::Writer::from_writer(&mut writer);
+            csv.write_record(std::iter::once(&"path").chain(T::CSV_HEADER))?;
+            csv.flush()?;
+        }
+        Ok(Printer {
+            format,
+            multiple,
+            writer,
+            json: Vec::new(),
+            output: PhantomData,
+        })
+    }
+
+    /// Failed images only appear in JSON output, as an `error` object.
+    pub fn print(&mut self, path: &Path, result: &TessResult<T>) -> Result<(), CliError> {
+        let path = path.display().to_string();
+        match (self.format, result) {
+            (Format::Json, Ok(output)) => self.json.push(json!({"path": path, "output": output})),
+            (Format::Json, Err(error)) => self.json.push(json!({"path": path, "error": error})),
+            (Format::Text, Ok(output)) => {
+                if self.multiple {
+                    writeln!(self.writer, "==> {} <==", path)?;
+                }
+                write_text(&mut self.writer, output.text())?;
+            }
+            (Format::Csv, Ok(output)) => {
+                let mut writer = csv::Writer::from_writer(&mut self.writer);
+                for record in output.csv_records() {
+                    writer.write_record(std::iter::once(&path).chain(&record))?;
+                }
+                writer.flush()?;
+            }
+            (_, Err(_)) => {}
+        }
+        Ok(())
+    }
+
+    pub fn finish(mut self) -> Result<(), CliError> {
+        if self.format == Format::Json {
+            write_json(&mut self.writer, &self.json)?;
+        }
+        Ok(self.writer.flush()?)
+    }
+}
+
+/// Writes one line per watch event as soon as it happens: plain text, a JSON
+/// object per line or a CSV record. Failures also go to stderr.
+pub struct WatchPrinter {
+    format: Format,
+    stdout: Stdout,
+    csv: Option<csv::Writer<Stdout>>,
+}
+
+impl WatchPrinter {
+    pub fn new(format: Format) -> Result<Self, CliError> {
+        let csv = match format {
+            Format::Csv => {
+                let mut writer = csv::Writer::from_writer(io::stdout());
+                writer.write_record(["image", "sidecars", "error"])?;
+                writer.flush()?;
+                Some(writer)
+            }
+            _ => None,
+        };
+        Ok(WatchPrinter {
+            format,
+            stdout: io::stdout(),
+            csv,
+        })
+    }
+
+    pub fn print(&mut self, event: &WatchEvent) -> Result<(), CliError> {
+        let (image, sidecars, error) = match event {
+            WatchEvent::Processed { image, sidecars } => (image, sidecars.as_slice(), None),
+            WatchEvent::Failed { image, error } => {
+                eprintln!("rusty-tesseract: {}: {}", image.display(), error);
+                (image, [].as_slice(), Some(error))
+            }
+        };
+        let sidecars = sidecars
+            .iter()
+            .map(|x| x.display().to_string())
+            .collect::<Vec<_>>();
+
+        match self.format {
+            Format::Text => {
+                if error.is_none() {
+                    writeln!(
+                        self.stdout,
+                        "{} -> {}",
+                        image.display(),
+                        sidecars.join(", ")
+                    )?;
+                }
+            }
+            Format::Json => {
+                let line = match error {
+                    Some(error) => json!({"image": image, "error": error}),
+                    None => json!({"image": image, "sidecars": sidecars}),
+                };
+                serde_json::to_writer(&mut self.stdout, &line).map_err(io::Error::from)?;
+                writeln!(self.stdout)?;
+            }
+            Format::Csv => {
+                if let Some(writer) = &mut self.csv {
+                    writer.write_record([
+                        image.display().to_string(),
+                        sidecars.join(" "),
+                        error.map_or_else(String::new, |x| x.to_string()),
+                    ])?;
+                    writer.flush()?;
+                }
+            }
+        }
+        Ok(self.stdout.flush()?)
+    }
+}
+
+pub fn print_version(version: &str, format: Format) -> Result<(), CliError> {
+    let number = TesseractVersion::from_version_output(version)?;
+    match format {
+        Format::Text => Ok(write_text(&mut io::stdout(), version)?),
+        Format::Json => write_json(
+            &mut io::stdout(),
+            &json!({"version": version, "number": number}),
+        ),
+        Format::Csv => write_csv(&["version"], [[number.to_string()]]),
+    }
+}
+
+pub fn print_langs(langs: &[String], format: Format) -> Result<(), CliError> {
+    match format {
+        Format::Text => Ok(write_text(&mut io::stdout(), &langs.join("\n"))?),
+        Format::Json => write_json(&mut io::stdout(), &langs),
+        Format::Csv => write_csv(&["lang"], langs.iter().map(|x| [x])),
+    }
+}
+
+pub fn print_params(
+    parameters: &ConfigParameterOutput,
+    query: Option<&str>,
+    format: Format,
+) -> Result<(), CliError> {
+    let parameters = match query {
+        Some(query) => parameters.search(query),
+        None => parameters.config_parameters.iter().collect(),
+    };
+    match format {
+        Format::Text => {
+            let mut stdout = io::stdout();
+            for x in parameters {
+                writeln!(stdout, "{}\t{}\t{}", x.name, x.default_value, x.description)?;
+            }
+            Ok(())
+        }
+        Format::Json => write_json(&mut io::stdout(), &parameters),
+        Format::Csv => write_csv(
+            &["name", "default_value", "description"],
+            parameters.iter().map(|x| {
+                [
+                    x.name.clone(),
+                    x.default_value.to_string(),
+                    x.description.clone(),
+                ]
+            }),
+        ),
+    }
+}
+
+/// Writes `text` with a trailing newline.
+fn write_text(writer: &mut impl Write, text: &str) -> io::Result<()> {
+    writer.write_all(text.as_bytes())?;
+    if !text.ends_with('\n') {
+        writer.write_all(b"\n")?;
+    }
+    Ok(())
+}
+
+fn write_json(writer: &mut impl Write, value: &impl Serialize) -> Result<(), CliError> {
+    serde_json::to_writer_pretty(&mut *writer, value).map_err(io::Error::from)?;
+    Ok(writeln!(writer)?)
+}
+
+fn write_csv<R, F>(header: &[&str], records: R) -> Result<(), CliError>
+where
+    R: IntoIterator,
+    R::Item: IntoIterator<Item = F>,
+    F: AsRef<[u8]>,
+{
+    let mut writer = csv::Writer::from_writer(io::stdout());
+    writer.write_record(header)?;
+    for record in records {
+        writer.write_record(record)?;
+    }
+    Ok(writer.flush()?)
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use crate::cli::fake_tesseract::FakeTesseract;
+    use rusty_tesseract::{Args, Image, TessError};
+
+    /// Data output of a tesseract stand-in that prints a fixed TSV.
+    fn data_output() -> TessResult<DataOutput> {
+        let fake = FakeTesseract::new(
+            "printf 'level\\tpage_num\\tblock_num\\tpar_num\\tline_num\\tword_num\\tleft\\ttop\\twidth\\theight\\tconf\\ttext\\n\
+            1\\t1\\t0\\t0\\t0\\t0\\t0\\t0\\t696\\t89\\t-1\\t\\n\
+            5\\t1\\t1\\t1\\t1\\t1\\t18\\t29\\t144\\t35\\t95.5\\tLOREM\\n'",
+        );
+        let image = Image::from_path("img/string.png").unwrap();
+        fake.tesseract.image_to_data(&image, &Args::default())
+    }
+
+    fn print(format: Format, results: &[(&str, TessResult<DataOutput>)]) -> String {
+        let mut output = Vec::new();
+        let mut printer = Printer::with_writer(&mut output, format, results.len() > 1).unwrap();
+        for (path, result) in results {
+            printer.print(Path::new(path), result).unwrap();
+        }
+        printer.finish().unwrap();
+        String::from_utf8(output).unwrap()
+    }
+
+    #[test]
+    fn test_printer_json() {
+        let output = print(
+            Format::Json,
+            &[
+                ("a.png", data_output()),
+                ("b.png", Err(TessError::ImageNotFoundError)),
+            ],
+        );
+        let value: serde_json::Value = serde_json::from_str(&output).unwrap();
+
+        assert_eq!(value.as_array().unwrap().len(), 2);
+        assert_eq!(value[0]["path"], "a.png");
+        assert_eq!(value[0]["output"]["data"][1]["text"], "LOREM");
+        assert_eq!(value[0]["output"]["data"][1]["width"], 144);
+        assert_eq!(
+            value[1],
+            json!({"path": "b.png", "error": {"kind": "ImageNotFoundError"}})
+        );
+    }
+
+    #[test]
+    fn test_printer_csv() {
+        let output = print(
+            Format::Csv,
+            &[
+                ("a.png", data_output()),
+                ("b.png", Err(TessError::ImageNotFoundError)),
+            ],
+        );
+
+        assert_eq!(
+            output,
+            "path,level,page_num,block_num,par_num,line_num,word_num,left,top,width,height,conf,text\n\
+            a.png,1,1,0,0,0,0,0,0,696,89,-1,\n\
+            a.png,5,1,1,1,1,1,18,29,144,35,95.5,LOREM\n"
+        );
+    }
+}
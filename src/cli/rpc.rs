@@ -0,0 +1,527 @@
+//! JSON-RPC 2.0 over stdio: one request per line on stdin, one response per
+//! line on stdout.
+
+use super::{exit_code, load_image, CliError};
+use base64::{engine::general_purpose::STANDARD, Engine};
+use rusty_tesseract::{
+    Args, CancellationToken, Image, PdfOptions, TessError, TessResult, Tesseract, TesseractVersion,
+};
+use serde::{Deserialize, Deserializer};
+use serde_json::{json, Value};
+use std::{
+    collections::{hash_map::Entry, HashMap},
+    io::{self, BufRead, Write},
+    path::PathBuf,
+    sync::{mpsc, Mutex},
+    thread,
+};
+
+const PARSE_ERROR: i64 = -32700;
+const INVALID_REQUEST: i64 = -32600;
+const METHOD_NOT_FOUND: i64 = -32601;
+const INVALID_PARAMS: i64 = -32602;
+
+/// Error code for a failed call, within the `-32000` to `-32099` range
+/// reserved for server errors. The `sysexits.h` exit codes of the
+/// command-line interface count down from `-32001` for 64, e.g. `-32006` for
+/// a missing tesseract. Timeouts are `-32098` and cancelled calls `-32099`.
+pub fn error_code(error: &TessError) -> i64 {
+    match exit_code(error) {
+        124 => -32098,
+        130 => -32099,
+        code => -32000 - (i64::from(code) - 63),
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct Request {
+    jsonrpc: String,
+    /// Absent for notifications, which get no response. An explicit `null`
+    /// is kept as an id and answered.
+    #[serde(default, deserialize_with = "present")]
+    id: Option<Value>,
+    method: String,
+    #[serde(default)]
+    params: Value,
+}
+
+/// Tells a present `null` from an absent member, which `#[serde(default)]`
+/// turns into `None`.
+fn present<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<Value>, D::Error> {
+    Value::deserialize(deserializer).map(Some)
+}
+
+#[derive(Debug, Deserialize)]
+struct RecognizeParams {
+    image: ImageSource,
+    #[serde(default)]
+    output: OutputType,
+    #[serde(default)]
+    args: Args,
+    /// Only used for `pdf` output.
+    #[serde(default)]
+    pdf: PdfOptions,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum ImageSource {
+    Path(PathBuf),
+    /// Encoded image file, e.g. a PNG.
+    Base64(String),
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum OutputType {
+    #[default]
+    String,
+    Boxes,
+    Data,
+    Hocr,
+    Alto,
+    Osd,
+    PageXml,
+    Aligned,
+    /// The document is returned base64 encoded.
+    Pdf,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct ParamsParams {
+    query: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct CancelParams {
+    id: Value,
+}
+
+/// Failed request, sent as the `error` member of the response.
+#[derive(Debug)]
+struct RpcError {
+    code: i64,
+    message: String,
+    data: Option<TessError>,
+}
+
+impl From<TessError> for RpcError {
+    fn from(error: TessError) -> Self {
+        RpcError {
+            code: error_code(&error),
+            message: error.to_string(),
+            data: Some(error),
+        }
+    }
+}
+
+impl RpcError {
+    fn new(code: i64, message: impl Into<String>) -> Self {
+        RpcError {
+            code,
+            message: message.into(),
+            data: None,
+        }
+    }
+
+    fn to_json(&self) -> Value {
+        match &self.data {
+            Some(data) => json!({"code": self.code, "message": self.message, "data": data}),
+            None => json!({"code": self.code, "message": self.message}),
+        }
+    }
+}
+
+/// Request waiting for a worker.
+struct Job {
+    id: Option<Value>,
+    method: String,
+    params: Value,
+    cancellation: CancellationToken,
+}
+
+/// Cancellation tokens of the requests that did not finish yet, by id. A
+/// finished request only removes its own token, since a cancelled id may
+/// already be reused by a newer request.
+type Pending = Mutex<HashMap<String, CancellationToken>>;
+
+/// Answers the requests read from `input` until it ends. Up to `jobs`
+/// requests run at the same time, so responses may arrive in a different
+/// order than their requests. Requests still running when `input` ends are
+/// answered before returning.
+pub fn serve(
+    tesseract: &Tesseract,
+    jobs: usize,
+    input: impl BufRead,
+    output: impl Write + Send,
+) -> Result<(), CliError> {
+    let output = Mutex::new(output);
+    let pending = Pending::default();
+    let (sender, receiver) = mpsc::channel::<Job>();
+    let receiver = Mutex::new(receiver);
+
+    thread::scope(|scope| {
+        for _ in 0..jobs.max(1) {
+            scope.spawn(|| loop {
+                // the lock is released before the job runs
+                let job = receiver.lock().unwrap().recv();
+                let Ok(job) = job else { break };
+                let result = call(tesseract, &job.method, job.params, job.cancellation.clone());
+                if let Some(id) = &job.id {
+                    let mut pending = pending.lock().unwrap();
+                    let key = id.to_string();
+                    if pending.get(&key) == Some(&job.cancellation) {
+                        pending.remove(&key);
+                    }
+                }
+                // a client that stopped reading also closes stdin, which ends
+                // the requests
+                let _ = respond(&output, job.id, result);
+            });
+        }
+        // the workers stop once the sender is dropped at the end of input
+        read_requests(input, sender, &pending, &output)
+    })
+}
+
+fn read_requests(
+    input: impl BufRead,
+    sender: mpsc::Sender<Job>,
+    pending: &Pending,
+    output: &Mutex<impl Write>,
+) -> Result<(), CliError> {
+    for line in input.lines() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let request = match parse_request(&line) {
+            Ok(x) => x,
+            Err((id, error)) => {
+                respond(output, Some(id), Err(error))?;
+                continue;
+            }
+        };
+
+        // answered right away, so it does not wait behind running requests
+        if request.method == "cancel" {
+            let result = params::<CancelParams>(request.params).map(|x| {
+                match pending.lock().unwrap().remove(&x.id.to_string()) {
+                    Some(token) => {
+                        token.cancel();
+                        true.into()
+                    }
+                    None => false.into(),
+                }
+            });
+            respond(output, request.id, result)?;
+            continue;
+        }
+
+        let cancellation = CancellationToken::new();
+        if let Some(id) = &request.id {
+            let message = format!("request {} is still running", id);
+            let duplicate = match pending.lock().unwrap().entry(id.to_string()) {
+                Entry::Occupied(_) => true,
+                Entry::Vacant(x) => {
+                    x.insert(cancellation.clone());
+                    false
+                }
+            };
+            if duplicate {
+                let error = RpcError::new(INVALID_REQUEST, message);
+                respond(output, request.id, Err(error))?;
+                continue;
+            }
+        }
+        let job = Job {
+            id: request.id,
+            method: request.method,
+            params: request.params,
+            cancellation,
+        };
+        sender
+            .send(job)
+            .map_err(|_| io::Error::new(io::ErrorKind::BrokenPipe, "no worker is running"))?;
+    }
+    Ok(())
+}
+
+/// Splits a line into a request, or the error response for it with the id
+/// if one could be read.
+fn parse_request(line: &str) -> Result<Request, (Value, RpcError)> {
+    let value: Value = serde_json::from_str(line)
+        .map_err(|e| (Value::Null, RpcError::new(PARSE_ERROR, e.to_string())))?;
+    let id = value.get("id").cloned().unwrap_or(Value::Null);
+    let request: Request = serde_json::from_value(value)
+        .map_err(|e| (id.clone(), RpcError::new(INVALID_REQUEST, e.to_string())))?;
+    if request.jsonrpc != "2.0" {
+        return Err((
+            id,
+            RpcError::new(INVALID_REQUEST, "jsonrpc must be \"2.0\""),
+        ));
+    }
+    Ok(request)
+}
+
+fn params<T: for<'de> Deserialize<'de>>(params: Value) -> Result<T, RpcError> {
+    // methods without required parameters may omit them
+    let params = match params {
+        Value::Null => json!({}),
+        x => x,
+    };
+    serde_json::from_value(params).map_err(|e| {
+        let error = TessError::ArgumentError(e.to_string());
+        RpcError {
+            code: INVALID_PARAMS,
+            message: error.to_string(),
+            data: Some(error),
+        }
+    })
+}
+
+fn call(
+    tesseract: &Tesseract,
+    method: &str,
+    params: Value,
+    cancellation: CancellationToken,
+) -> Result<Value, RpcError> {
+    match method {
+        "recognize" => Ok(recognize(tesseract, self::params(params)?, cancellation)?),
+        "version" => {
+            let version = tesseract.get_tesseract_version()?;
+            let number = TesseractVersion::from_version_output(&version)?;
+            Ok(json!({"version": version, "number": number}))
+        }
+        "langs" => Ok(json!(tesseract.get_tesseract_langs()?)),
+        "params" => {
+            let query = self::params::<ParamsParams>(params)?.query;
+            let parameters = tesseract.get_tesseract_config_parameters()?;
+            Ok(match query {
+                Some(query) => json!(parameters.search(&query)),
+                None => json!(parameters.config_parameters),
+            })
+        }
+        _ => Err(RpcError::new(
+            METHOD_NOT_FOUND,
+            format!("unknown method '{}'", method),
+        )),
+    }
+}
+
+fn recognize(
+    tesseract: &Tesseract,
+    params: RecognizeParams,
+    cancellation: CancellationToken,
+) -> TessResult<Value> {
+    let image = match params.image {
+        ImageSource::Path(path) => load_image(&path)?,
+        ImageSource::Base64(data) => Image::from_bytes(
+            STANDARD
+                .decode(data)
+                .map_err(|e| TessError::ArgumentError(format!("invalid base64 image: {}", e)))?,
+        )?,
+    };
+    let args = Args {
+        cancellation: Some(cancellation),
+        ..params.args
+    };
+
+    Ok(match params.output {
+        OutputType::String => json!(tesseract.image_to_string(&image, &args)?),
+        OutputType::Boxes => json!(tesseract.image_to_boxes(&image, &args)?),
+        OutputType::Data => json!(tesseract.image_to_data(&image, &args)?),
+        OutputType::Hocr => json!(tesseract.image_to_hocr(&image, &args)?),
+        OutputType::Alto => json!(tesseract.image_to_alto(&image, &args)?),
+        OutputType::Osd => json!(tesseract.image_to_osd(&image, &args)?),
+        OutputType::PageXml => json!(tesseract.image_to_page_xml(&image, &args)?),
+        OutputType::Aligned => json!(tesseract.image_to_aligned(&image, &args)?),
+        OutputType::Pdf => {
+            let pdf = tesseract.image_to_pdf(&image, &args, &params.pdf)?;
+            json!({"output": STANDARD.encode(&pdf.output), "diagnostics": pdf.diagnostics})
+        }
+    })
+}
+
+/// Writes the response line, nothing for notifications.
+fn respond(
+    output: &Mutex<impl Write>,
+    id: Option<Value>,
+    result: Result<Value, RpcError>,
+) -> io::Result<()> {
+    let Some(id) = id else {
+        return Ok(());
+    };
+    let response = match result {
+        Ok(result) => json!({"jsonrpc": "2.0", "id": id, "result": result}),
+        Err(error) => json!({"jsonrpc": "2.0", "id": id, "error": error.to_json()}),
+    };
+    let mut output = output.lock().unwrap();
+    serde_json::to_writer(&mut *output, &response)?;
+    writeln!(output)?;
+    output.flush()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Responses by id for the given request lines.
+    fn run(tesseract: &Tesseract, requests: &[Value]) -> HashMap<String, Value> {
+        let input = requests
+            .iter()
+            .map(|x| format!("{}\n", x))
+            .collect::<String>();
+        let mut output = Vec::new();
+        serve(tesseract, 4, input.as_bytes(), &mut output).unwrap();
+        String::from_utf8(output)
+            .unwrap()
+            .lines()
+            .map(|x| {
+                let response: Value = serde_json::from_str(x).unwrap();
+                (response["id"].to_string(), response)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_rpc_errors() {
+        let tesseract = Tesseract::new("/nonexistent/tesseract");
+        let request = |id: i32, method: &str, params: Value| json!({"jsonrpc": "2.0", "id": id, "method": method, "params": params});
+        let responses = run(
+            &tesseract,
+            &[
+                request(1, "langs", Value::Null),
+                request(2, "recognize", json!({"image": {"path": "missing.png"}})),
+                request(3, "recognize", json!({"image": {"base64": "%%%"}})),
+                request(4, "recognize", json!({"image": "img/string.png"})),
+                request(5, "ocr", Value::Null),
+                request(6, "cancel", json!({"id": 1})),
+                json!({"jsonrpc": "1.0", "id": 7, "method": "langs"}),
+                // notifications are not answered, unlike a null id
+                json!({"jsonrpc": "2.0", "method": "langs"}),
+                json!({"jsonrpc": "2.0", "id": null, "method": "ocr"}),
+            ],
+        );
+        assert_eq!(responses.len(), 8);
+        assert_eq!(
+            responses["1"]["error"],
+            json!({
+                "code": -32006,
+                "message": "Tesseract not found. Please check installation path!",
+                "data": {"kind": "TesseractNotFoundError"}
+            })
+        );
+        assert_eq!(
+            responses["2"]["error"]["data"],
+            json!({"kind": "ImageNotFoundError"})
+        );
+        assert_eq!(responses["3"]["error"]["code"], json!(-32001));
+        assert_eq!(responses["4"]["error"]["code"], json!(INVALID_PARAMS));
+        assert_eq!(responses["5"]["error"]["code"], json!(METHOD_NOT_FOUND));
+        // the langs request already finished or is not cancellable
+        assert!(responses["6"]["result"].is_boolean());
+        assert_eq!(responses["7"]["error"]["code"], json!(INVALID_REQUEST));
+        assert_eq!(responses["null"]["error"]["code"], json!(METHOD_NOT_FOUND));
+
+        // a JSON string is no request object
+        let responses = run(&tesseract, &[json!("{")]);
+        assert_eq!(responses["null"]["error"]["code"], json!(INVALID_REQUEST));
+
+        let mut output = Vec::new();
+        serve(&tesseract, 1, "{\n".as_bytes(), &mut output).unwrap();
+        let response: Value = serde_json::from_slice(&output).unwrap();
+        assert_eq!(response["error"]["code"], json!(PARSE_ERROR));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_rpc_cancel() {
+        use crate::cli::fake_tesseract::FakeTesseract;
+
+        let fake = FakeTesseract::new("exec sleep 10");
+
+        let recognize = json!({"jsonrpc": "2.0", "id": 1, "method": "recognize", "params": {"image": {"path": "img/string.png"}}});
+        let input = format!(
+            "{}\n{}\n{}\n",
+            recognize,
+            recognize,
+            json!({"jsonrpc": "2.0", "id": 2, "method": "cancel", "params": {"id": 1}}),
+        );
+        let mut output = Vec::new();
+        serve(&fake.tesseract, 1, input.as_bytes(), &mut output).unwrap();
+        let responses = String::from_utf8(output)
+            .unwrap()
+            .lines()
+            .map(|x| serde_json::from_str::<Value>(x).unwrap())
+            .collect::<Vec<_>>();
+
+        // the duplicate id and the cancel are answered without waiting for
+        // the recognition
+        assert_eq!(
+            responses,
+            vec![
+                json!({
+                    "jsonrpc": "2.0",
+                    "id": 1,
+                    "error": {"code": INVALID_REQUEST, "message": "request 1 is still running"}
+                }),
+                json!({"jsonrpc": "2.0", "id": 2, "result": true}),
+                json!({
+                    "jsonrpc": "2.0",
+                    "id": 1,
+                    "error": {
+                        "code": -32099,
+                        "message": "Tesseract was cancelled.",
+                        "data": {"kind": "CancelledError"}
+                    }
+                }),
+            ]
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_rpc_cancel_reused_id() {
+        use crate::cli::fake_tesseract::FakeTesseract;
+        use std::time::{Duration, Instant};
+
+        let fake = FakeTesseract::new("exec sleep 10");
+        let recognize = json!({"jsonrpc": "2.0", "id": 1, "method": "recognize", "params": {"image": {"path": "img/string.png"}}});
+        let cancel =
+            |id: i32| json!({"jsonrpc": "2.0", "id": id, "method": "cancel", "params": {"id": 1}});
+        let (reader, mut writer) = io::pipe().unwrap();
+        let mut output = Vec::new();
+
+        let started = Instant::now();
+        thread::scope(|scope| {
+            let server = scope.spawn(|| {
+                serve(&fake.tesseract, 2, io::BufReader::new(reader), &mut output).unwrap()
+            });
+            writeln!(writer, "{}\n{}\n{}", recognize, cancel(2), recognize).unwrap();
+            // the cancelled request finishes after its id was reused and
+            // must leave the newer request cancellable
+            thread::sleep(Duration::from_millis(500));
+            writeln!(writer, "{}", cancel(3)).unwrap();
+            drop(writer);
+            server.join().unwrap();
+        });
+        assert!(started.elapsed() < Duration::from_secs(5));
+
+        let responses = String::from_utf8(output)
+            .unwrap()
+            .lines()
+            .map(|x| serde_json::from_str::<Value>(x).unwrap())
+            .collect::<Vec<_>>();
+        let result = |id: i32| {
+            responses
+                .iter()
+                .filter(|x| x["id"] == json!(id))
+                .map(|x| x.get("result").unwrap_or(&x["error"]["code"]).clone())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(result(1), vec![json!(-32099), json!(-32099)]);
+        assert_eq!(result(2), vec![json!(true)]);
+        assert_eq!(result(3), vec![json!(true)]);
+    }
+}
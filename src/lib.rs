@@ -1,4 +1,6 @@
 pub mod tesseract;
 
 pub use image;
+#[cfg(feature = "polars")]
+pub use polars;
 pub use tesseract::*;
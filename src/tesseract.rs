@@ -1,16 +1,68 @@
+pub mod alignment;
+#[cfg(feature = "tokio")]
+pub mod asynchronous;
+pub mod cancellation;
 pub mod command;
+pub mod config_value;
+#[cfg(feature = "polars")]
+pub mod dataframe;
+pub mod diagnostics;
+pub mod document;
+pub mod engine;
 pub mod error;
+pub mod geometry;
 pub mod input;
+pub mod input_format;
+pub mod modes;
+pub mod output_alto;
 pub mod output_boxes;
 pub mod output_config_parameters;
 pub mod output_data;
+pub mod output_hocr;
+pub mod output_osd;
+pub mod output_page_xml;
+pub mod output_pdf;
+pub mod output_string;
+#[cfg(feature = "server")]
+pub mod server;
+pub mod version;
+#[cfg(feature = "watch")]
+pub mod watch;
 
+pub use alignment::*;
+#[cfg(feature = "tokio")]
+pub use asynchronous::*;
+pub use cancellation::*;
 pub use command::*;
+pub use config_value::*;
+pub use diagnostics::*;
+pub use document::*;
+pub use engine::*;
 pub use error::*;
+pub use geometry::*;
 pub use input::*;
+pub use input_format::*;
+pub use modes::*;
+pub use output_alto::*;
 pub use output_boxes::*;
 pub use output_config_parameters::*;
 pub use output_data::*;
+pub use output_hocr::*;
+pub use output_osd::*;
+pub use output_page_xml::*;
+pub use output_pdf::*;
+pub use output_string::*;
+#[cfg(feature = "server")]
+pub use server::*;
+pub use version::*;
+#[cfg(feature = "watch")]
+pub use watch::*;
 
+mod auto_rotate;
 mod parse_line_util;
+#[cfg(feature = "serde")]
+mod serde_util;
 use parse_line_util::*;
+
+#[cfg(test)]
+mod test_util;
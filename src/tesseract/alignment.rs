@@ -0,0 +1,259 @@
+use super::*;
+
+/// Symbols of a [`BoxOutput`] attached to the words of a [`DataOutput`]
+/// recognized from the same image.
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AlignedOutput {
+    pub words: Vec<AlignedWord>,
+    /// Symbols that do not lie within any word, in box file order.
+    pub unaligned: Vec<Symbol>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AlignedWord {
+    pub word: Data,
+    /// Symbols of the word in box file order.
+    pub characters: Vec<Symbol>,
+}
+
+/// Single symbol of a box file with a top-left origin like [`Data`].
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Symbol {
+    pub text: String,
+    /// Page number counting from 1, matching [`Data::page_num`].
+    pub page_num: i32,
+    /// `None` for symbols on pages missing from the data, whose height is
+    /// unknown.
+    pub bbox: Option<BoundingBox>,
+    /// Entry of the box file the symbol was read from.
+    pub raw: Box,
+}
+
+impl AlignedOutput {
+    /// Assigns every symbol to the word on its page that covers most of it.
+    /// Symbols overlapping no word by at least half their area are reported
+    /// in [`AlignedOutput::unaligned`].
+    pub fn new(boxes: &BoxOutput, data: &DataOutput) -> Self {
+        let mut words: Vec<AlignedWord> = data
+            .data
+            .iter()
+            .filter(|x| x.level == Level::Word.value() && !x.text.trim().is_empty())
+            .map(|x| AlignedWord {
+                word: x.clone(),
+                characters: Vec::new(),
+            })
+            .collect();
+        let mut unaligned = Vec::new();
+
+        for x in &boxes.boxes {
+            // box files count pages from 0 and y from the bottom of the page
+            let page_num = x.page + 1;
+            let Some(page) = data
+                .data
+                .iter()
+                .find(|x| x.level == Level::Page.value() && x.page_num == page_num)
+            else {
+                unaligned.push(Symbol {
+                    text: x.symbol.clone(),
+                    page_num,
+                    bbox: None,
+                    raw: x.clone(),
+                });
+                continue;
+            };
+            let bbox = x.bbox(page.height);
+            let symbol = Symbol {
+                text: x.symbol.clone(),
+                page_num,
+                bbox: Some(bbox),
+                raw: x.clone(),
+            };
+
+            match best_word(&mut words, page_num, &bbox) {
+                Some(word) => word.characters.push(symbol),
+                None => unaligned.push(symbol),
+            }
+        }
+
+        AlignedOutput { words, unaligned }
+    }
+
+    /// Words where the number of symbols differs from the recognized text.
+    pub fn incomplete_words(&self) -> impl Iterator<Item = &AlignedWord> {
+        self.words.iter().filter(|x| !x.is_complete())
+    }
+}
+
+impl AlignedWord {
+    pub fn character_boxes(&self) -> impl Iterator<Item = BoundingBox> + '_ {
+        self.characters.iter().filter_map(|x| x.bbox)
+    }
+
+    /// Whether there is one symbol for every non-whitespace character of the
+    /// word.
+    pub fn is_complete(&self) -> bool {
+        let symbols: usize = self.characters.iter().map(|x| x.text.chars().count()).sum();
+        symbols
+            == self
+                .word
+                .text
+                .chars()
+                .filter(|x| !x.is_whitespace())
+                .count()
+    }
+}
+
+fn best_word<'a>(
+    words: &'a mut [AlignedWord],
+    page_num: i32,
+    bbox: &BoundingBox,
+) -> Option<&'a mut AlignedWord> {
+    let (overlap, word) = words
+        .iter_mut()
+        .filter(|x| x.word.page_num == page_num)
+        .filter_map(|x| {
+            let overlap = x.word.bbox().intersection(bbox)?.area();
+            Some((overlap, x))
+        })
+        .max_by_key(|(overlap, _)| *overlap)?;
+    (overlap * 2 >= bbox.area()).then_some(word)
+}
+
+impl Tesseract {
+    /// Runs tesseract twice, for the box file and the TSV data, and aligns
+    /// the symbols to the words. With `auto_rotate`, OSD runs once and both
+    /// recognitions get the same upright image.
+    pub fn image_to_aligned(&self, image: &Image, args: &Args) -> TessResult<AlignedOutput> {
+        let upright = self.upright(image, args)?;
+        let upright_image = upright.as_ref().map_or(image, |x| &x.image);
+        let args = Args {
+            auto_rotate: false,
+            ..args.clone()
+        };
+
+        let mut boxes = self.image_to_boxes(upright_image, &args)?;
+        let mut data = self.image_to_data(upright_image, &args)?;
+        if let Some(upright) = &upright {
+            boxes.unrotate(upright);
+            data.unrotate(upright);
+        }
+        Ok(AlignedOutput::new(&boxes, &data))
+    }
+}
+
+pub fn image_to_aligned(image: &Image, args: &Args) -> TessResult<AlignedOutput> {
+    Tesseract::shared().image_to_aligned(image, args)
+}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(unix)]
+    use crate::tesseract::test_util::FakeTesseract;
+    use crate::tesseract::test_util::{self, box_output};
+    use crate::*;
+
+    fn data_output() -> DataOutput {
+        test_util::data_output(
+            "1\t1\t0\t0\t0\t0\t0\t0\t696\t89\t-1\t\n\
+                2\t1\t1\t0\t0\t0\t18\t29\t653\t35\t-1\t\n\
+                3\t1\t1\t1\t0\t0\t18\t29\t653\t35\t-1\t\n\
+                4\t1\t1\t1\t1\t0\t18\t29\t653\t35\t-1\t\n\
+                5\t1\t1\t1\t1\t1\t490\t29\t50\t35\t95.873787\tSIT\n\
+                5\t1\t1\t1\t1\t2\t553\t30\t118\t33\t96.834381\tAMET\n",
+        )
+    }
+
+    #[test]
+    fn test_aligned_output() {
+        let boxes = box_output(
+            "S 490 25 511 60 0\n\
+            I 514 26 518 59 0\n\
+            T 521 26 540 59 0\n\
+            A 553 26 586 59 0\n\
+            M 589 26 624 59 0\n\
+            E 630 26 649 59 0\n\
+            T 652 26 671 59 0\n\
+            . 680 26 684 30 0\n\
+            X 10 10 20 20 1",
+        );
+        let aligned = AlignedOutput::new(&boxes, &data_output());
+
+        assert_eq!(aligned.words.len(), 2);
+        assert_eq!(
+            aligned.words[0]
+                .characters
+                .iter()
+                .map(|x| x.text.as_str())
+                .collect::<Vec<_>>(),
+            vec!["S", "I", "T"]
+        );
+        assert_eq!(
+            aligned.words[0].character_boxes().next(),
+            Some(BoundingBox::new(490, 29, 511, 64))
+        );
+        assert_eq!(aligned.words[1].characters.len(), 4);
+        assert_eq!(aligned.incomplete_words().count(), 0);
+
+        assert_eq!(
+            aligned
+                .unaligned
+                .iter()
+                .map(|x| (x.text.as_str(), x.page_num))
+                .collect::<Vec<_>>(),
+            vec![(".", 1), ("X", 2)]
+        );
+        assert_eq!(
+            aligned.unaligned[0].bbox,
+            Some(BoundingBox::new(680, 59, 684, 63))
+        );
+        assert_eq!(aligned.unaligned[1].bbox, None);
+        assert_eq!(aligned.unaligned[1].raw, boxes.boxes[8]);
+    }
+
+    #[test]
+    fn test_aligned_output_incomplete_word() {
+        let boxes = box_output("S 490 25 511 60 0\nT 521 26 540 59 0");
+        let aligned = AlignedOutput::new(&boxes, &data_output());
+
+        assert_eq!(
+            aligned
+                .incomplete_words()
+                .map(|x| x.word.text.as_str())
+                .collect::<Vec<_>>(),
+            vec!["SIT", "AMET"]
+        );
+        assert!(aligned.unaligned.is_empty());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_image_to_aligned_auto_rotate() {
+        let fake = FakeTesseract::new(
+            r#"case "$*" in
+    *--list-langs*) printf 'List of available languages (2):\neng\nosd\n' ;;
+    *"--psm 0"*) echo osd >> "$0.log"; printf 'Page number: 0\nOrientation in degrees: 270\nRotate: 90\nOrientation confidence: 9.58\nScript: Latin\nScript confidence: 2.25\n' ;;
+    *makebox) printf 'L 45 90 50 100 0\n' ;;
+    *tsv) printf 'level\tpage_num\tblock_num\tpar_num\tline_num\tword_num\tleft\ttop\twidth\theight\tconf\ttext\n1\t1\t0\t0\t0\t0\t0\t0\t50\t100\t-1\t\n5\t1\t1\t1\t1\t1\t45\t0\t5\t10\t90\tL\n' ;;
+esac"#,
+        );
+        let original = image::DynamicImage::new_luma8(100, 50);
+        let image = Image::from_dynamic_image_via_stdin(&original, StdinFormat::Pnm).unwrap();
+        let args = Args {
+            auto_rotate: true,
+            ..Default::default()
+        };
+
+        let aligned = fake.tesseract.image_to_aligned(&image, &args).unwrap();
+        assert_eq!(aligned.words[0].word.bbox(), BoundingBox::new(0, 0, 10, 5));
+        assert_eq!(
+            aligned.words[0].character_boxes().collect::<Vec<_>>(),
+            vec![BoundingBox::new(0, 0, 10, 5)]
+        );
+
+        let log = fake.tesseract.executable().with_extension("log");
+        assert_eq!(std::fs::read_to_string(log).unwrap(), "osd\n");
+    }
+}
@@ -0,0 +1,402 @@
+use super::*;
+use std::process::Command;
+use std::time::Duration;
+use tokio::io::AsyncWriteExt;
+
+impl Tesseract {
+    pub async fn get_tesseract_version_async(&self) -> TessResult<String> {
+        if let Some(version) = self.version.get() {
+            return Ok(version.clone());
+        }
+        let mut command = self.command();
+        command.arg("--version");
+
+        let output = run_tesseract_command_async(command, RunOptions::default()).await?;
+        Ok(self.version.get_or_init(|| output.stdout).clone())
+    }
+
+    pub async fn get_tesseract_version_number_async(&self) -> TessResult<TesseractVersion> {
+        TesseractVersion::from_version_output(&self.get_tesseract_version_async().await?)
+    }
+
+    pub async fn get_tesseract_langs_async(&self) -> TessResult<Vec<String>> {
+        if let Some(langs) = self.langs.get() {
+            return Ok(langs.clone());
+        }
+        let mut command = self.command();
+        command.arg("--list-langs");
+
+        let output = run_tesseract_command_async(command, RunOptions::default()).await?;
+        Ok(self
+            .langs
+            .get_or_init(|| parse_langs(&output.stdout))
+            .clone())
+    }
+
+    pub async fn get_tesseract_config_parameters_async(&self) -> TessResult<ConfigParameterOutput> {
+        if let Some(parameters) = self.config_parameters.get() {
+            return Ok(parameters.clone());
+        }
+        let mut command = self.command();
+        command.arg("--print-parameters");
+
+        let output = run_tesseract_command_async(command, RunOptions::default()).await?;
+        let parameters = ConfigParameterOutput::from_output(output)?;
+        Ok(self.config_parameters.get_or_init(|| parameters).clone())
+    }
+
+    pub async fn image_to_string_async(
+        &self,
+        image: &Image,
+        args: &Args,
+    ) -> TessResult<StringOutput> {
+        let upright = self.upright_async(image, args).await?;
+        let image = upright.as_ref().map_or(image, |x| &x.image);
+
+        let command = self.create_tesseract_command(image, args)?;
+        let output = run_tesseract_command_async(command, RunOptions::new(image, args)).await?;
+        Ok(StringOutput::from_output(output))
+    }
+
+    pub async fn image_to_boxes_async(&self, image: &Image, args: &Args) -> TessResult<BoxOutput> {
+        let upright = self.upright_async(image, args).await?;
+        let image = upright.as_ref().map_or(image, |x| &x.image);
+
+        let mut command = self.create_tesseract_command(image, args)?;
+        command.arg("makebox");
+
+        let output = run_tesseract_command_async(command, RunOptions::new(image, args)).await?;
+        let mut output = BoxOutput::from_output(output)?;
+        if let Some(upright) = &upright {
+            output.unrotate(upright);
+        }
+        Ok(output)
+    }
+
+    pub async fn image_to_data_async(&self, image: &Image, args: &Args) -> TessResult<DataOutput> {
+        let upright = self.upright_async(image, args).await?;
+        let image = upright.as_ref().map_or(image, |x| &x.image);
+
+        let mut command = self.create_tesseract_command(image, args)?;
+        command.arg("tsv");
+
+        let output = run_tesseract_command_async(command, RunOptions::new(image, args)).await?;
+        let mut output = DataOutput::from_output(output)?;
+        if let Some(upright) = &upright {
+            output.unrotate(upright);
+        }
+        Ok(output)
+    }
+
+    pub async fn image_to_aligned_async(
+        &self,
+        image: &Image,
+        args: &Args,
+    ) -> TessResult<AlignedOutput> {
+        let upright = self.upright_async(image, args).await?;
+        let upright_image = upright.as_ref().map_or(image, |x| &x.image);
+        let args = Args {
+            auto_rotate: false,
+            ..args.clone()
+        };
+
+        let (mut boxes, mut data) = tokio::try_join!(
+            self.image_to_boxes_async(upright_image, &args),
+            self.image_to_data_async(upright_image, &args)
+        )?;
+        if let Some(upright) = &upright {
+            boxes.unrotate(upright);
+            data.unrotate(upright);
+        }
+        Ok(AlignedOutput::new(&boxes, &data))
+    }
+
+    pub async fn image_to_hocr_async(&self, image: &Image, args: &Args) -> TessResult<HocrOutput> {
+        let mut command = self.create_tesseract_command(image, args)?;
+        command.arg("hocr");
+
+        let output = run_tesseract_command_async(command, RunOptions::new(image, args)).await?;
+        HocrOutput::from_output(output)
+    }
+
+    pub async fn image_to_alto_async(&self, image: &Image, args: &Args) -> TessResult<AltoOutput> {
+        let mut command = self.create_tesseract_command(image, args)?;
+        command.arg("alto");
+
+        let output = run_tesseract_command_async(command, RunOptions::new(image, args)).await?;
+        AltoOutput::from_output(output)
+    }
+
+    pub async fn image_to_osd_async(&self, image: &Image, args: &Args) -> TessResult<OsdOutput> {
+        check_osd_installed(&self.get_tesseract_langs_async().await?)?;
+        let command = self.create_osd_command(image, args)?;
+
+        let output = run_tesseract_command_async(command, RunOptions::new(image, args)).await?;
+        OsdOutput::from_output(output)
+    }
+
+    pub async fn image_to_page_xml_async(
+        &self,
+        image: &Image,
+        args: &Args,
+    ) -> TessResult<PageXmlOutput> {
+        self.get_tesseract_version_number_async()
+            .await?
+            .require(PAGE_XML_MIN_VERSION, "PAGE XML")?;
+        let command = self.create_page_xml_command(image, args)?;
+
+        let output = run_tesseract_command_async(command, RunOptions::new(image, args)).await?;
+        PageXmlOutput::from_output(output)
+    }
+
+    pub async fn image_to_pdf_async(
+        &self,
+        image: &Image,
+        args: &Args,
+        options: &PdfOptions,
+    ) -> TessResult<PdfOutput> {
+        self.images_to_pdf_async(std::slice::from_ref(image), args, options)
+            .await
+    }
+
+    pub async fn images_to_pdf_async(
+        &self,
+        images: &[Image],
+        args: &Args,
+        options: &PdfOptions,
+    ) -> TessResult<PdfOutput> {
+        let input = PdfInput::new(images)?;
+        let command = self.create_pdf_command(&input, args, options)?;
+
+        let output =
+            run_tesseract_command_bytes_async(command, RunOptions::from_args(args)).await?;
+        Ok(PdfOutput::from_output(output))
+    }
+}
+
+pub async fn image_to_aligned_async(image: &Image, args: &Args) -> TessResult<AlignedOutput> {
+    Tesseract::shared()
+        .image_to_aligned_async(image, args)
+        .await
+}
+
+pub async fn image_to_hocr_async(image: &Image, args: &Args) -> TessResult<HocrOutput> {
+    Tesseract::shared().image_to_hocr_async(image, args).await
+}
+
+pub async fn image_to_alto_async(image: &Image, args: &Args) -> TessResult<AltoOutput> {
+    Tesseract::shared().image_to_alto_async(image, args).await
+}
+
+pub async fn image_to_osd_async(image: &Image, args: &Args) -> TessResult<OsdOutput> {
+    Tesseract::shared().image_to_osd_async(image, args).await
+}
+
+pub async fn image_to_page_xml_async(image: &Image, args: &Args) -> TessResult<PageXmlOutput> {
+    Tesseract::shared()
+        .image_to_page_xml_async(image, args)
+        .await
+}
+
+pub async fn image_to_pdf_async(
+    image: &Image,
+    args: &Args,
+    options: &PdfOptions,
+) -> TessResult<PdfOutput> {
+    Tesseract::shared()
+        .image_to_pdf_async(image, args, options)
+        .await
+}
+
+pub async fn images_to_pdf_async(
+    images: &[Image],
+    args: &Args,
+    options: &PdfOptions,
+) -> TessResult<PdfOutput> {
+    Tesseract::shared()
+        .images_to_pdf_async(images, args, options)
+        .await
+}
+
+pub async fn get_tesseract_version_async() -> TessResult<String> {
+    Tesseract::shared().get_tesseract_version_async().await
+}
+
+pub async fn get_tesseract_version_number_async() -> TessResult<TesseractVersion> {
+    Tesseract::shared()
+        .get_tesseract_version_number_async()
+        .await
+}
+
+pub async fn get_tesseract_langs_async() -> TessResult<Vec<String>> {
+    Tesseract::shared().get_tesseract_langs_async().await
+}
+
+pub async fn get_tesseract_config_parameters_async() -> TessResult<ConfigParameterOutput> {
+    Tesseract::shared()
+        .get_tesseract_config_parameters_async()
+        .await
+}
+
+pub async fn image_to_string_async(image: &Image, args: &Args) -> TessResult<StringOutput> {
+    Tesseract::shared().image_to_string_async(image, args).await
+}
+
+pub async fn image_to_boxes_async(image: &Image, args: &Args) -> TessResult<BoxOutput> {
+    Tesseract::shared().image_to_boxes_async(image, args).await
+}
+
+pub async fn image_to_data_async(image: &Image, args: &Args) -> TessResult<DataOutput> {
+    Tesseract::shared().image_to_data_async(image, args).await
+}
+
+pub(crate) async fn run_tesseract_command_async(
+    command: Command,
+    options: RunOptions<'_>,
+) -> TessResult<ProcessOutput> {
+    run_tesseract_command_bytes_async(command, options)
+        .await?
+        .into_text()
+}
+
+pub(crate) async fn run_tesseract_command_bytes_async(
+    mut command: Command,
+    options: RunOptions<'_>,
+) -> TessResult<ProcessOutput<Vec<u8>>> {
+    prepare_tesseract_command(&mut command, options.stdin.is_some());
+
+    let mut child = tokio::process::Command::from(command)
+        .kill_on_drop(true)
+        .spawn()
+        .map_err(spawn_error)?;
+
+    let stdin = child.stdin.take().zip(options.stdin);
+    let run = async move {
+        let write = async move {
+            // tesseract reports unreadable input through its exit status
+            if let Some((mut pipe, data)) = stdin {
+                let _ = pipe.write_all(&data).await;
+            }
+        };
+        let (_, output) = tokio::join!(write, child.wait_with_output());
+        output
+    };
+
+    // dropping the pending output future kills the child through kill_on_drop
+    let output = tokio::select! {
+        output = run => output.map_err(process_io_error)?,
+        timeout = expire(options.timeout) => return Err(TessError::TimeoutError(timeout)),
+        _ = cancelled(options.cancellation) => return Err(TessError::CancelledError),
+    };
+
+    process_tesseract_output(output)
+}
+
+async fn expire(timeout: Option<Duration>) -> Duration {
+    match timeout {
+        Some(timeout) => {
+            tokio::time::sleep(timeout).await;
+            timeout
+        }
+        None => std::future::pending().await,
+    }
+}
+
+async fn cancelled(cancellation: Option<&CancellationToken>) {
+    match cancellation {
+        Some(token) => {
+            while !token.is_cancelled() {
+                tokio::time::sleep(POLL_INTERVAL).await;
+            }
+        }
+        None => std::future::pending().await,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(unix)]
+    use crate::tesseract::test_util::FakeTesseract;
+    use crate::*;
+    use std::time::Duration;
+
+    #[tokio::test]
+    async fn test_missing_executable_async() {
+        let tesseract = Tesseract::new("/nonexistent/tesseract");
+        assert_eq!(
+            tesseract.get_tesseract_version_async().await,
+            Err(TessError::TesseractNotFoundError)
+        );
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_timeout_async() {
+        let fake = FakeTesseract::new("exec sleep 10");
+        let img = Image::from_path("img/string.png").unwrap();
+        let args = Args {
+            timeout: Some(Duration::from_millis(100)),
+            ..Default::default()
+        };
+
+        let started = std::time::Instant::now();
+        let result = fake.tesseract.image_to_string_async(&img, &args).await;
+        assert_eq!(
+            result,
+            Err(TessError::TimeoutError(Duration::from_millis(100)))
+        );
+        assert!(started.elapsed() < Duration::from_secs(5));
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_cancellation_async() {
+        let fake = FakeTesseract::new("exec sleep 10");
+        let img = Image::from_path("img/string.png").unwrap();
+        let token = CancellationToken::new();
+        let args = Args {
+            cancellation: Some(token.clone()),
+            ..Default::default()
+        };
+
+        token.cancel();
+        let result = fake.tesseract.image_to_string_async(&img, &args).await;
+        assert_eq!(result, Err(TessError::CancelledError));
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_image_to_aligned_async_runs_osd_once() {
+        let fake = FakeTesseract::new(
+            r#"case "$*" in
+    *--list-langs*) printf 'List of available languages (2):\neng\nosd\n' ;;
+    *"--psm 0"*) echo osd >> "$0.log"; printf 'Page number: 0\nOrientation in degrees: 270\nRotate: 90\nOrientation confidence: 9.58\nScript: Latin\nScript confidence: 2.25\n' ;;
+    *makebox) printf 'L 45 90 50 100 0\n' ;;
+    *tsv) printf 'level\tpage_num\tblock_num\tpar_num\tline_num\tword_num\tleft\ttop\twidth\theight\tconf\ttext\n5\t1\t1\t1\t1\t1\t45\t0\t5\t10\t90\tL\n' ;;
+esac"#,
+        );
+        let original = image::DynamicImage::new_luma8(100, 50);
+        let image = Image::from_dynamic_image_via_stdin(&original, StdinFormat::Pnm).unwrap();
+        let args = Args {
+            auto_rotate: true,
+            ..Default::default()
+        };
+
+        let aligned = fake
+            .tesseract
+            .image_to_aligned_async(&image, &args)
+            .await
+            .unwrap();
+        assert_eq!(aligned.words.len(), 1);
+
+        let log = fake.tesseract.executable().with_extension("log");
+        assert_eq!(std::fs::read_to_string(log).unwrap(), "osd\n");
+    }
+
+    #[tokio::test]
+    async fn test_image_to_string_async() {
+        let img = Image::from_path("img/string.png").unwrap();
+        let output = image_to_string_async(&img, &Args::default()).await.unwrap();
+        assert_eq!(output.trim(), "LOREM IPSUM DOLOR SIT AMET");
+    }
+}
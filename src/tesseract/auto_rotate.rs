@@ -0,0 +1,235 @@
+use super::*;
+
+/// Upright copy of an image that OSD found to be rotated.
+pub(crate) struct Upright {
+    pub image: Image,
+    /// Clockwise rotation in degrees applied to the original image.
+    rotate: i32,
+    width: i32,
+    height: i32,
+}
+
+impl Upright {
+    fn new(image: &Image, rotate: i32) -> TessResult<Option<Self>> {
+        let rotate = rotate.rem_euclid(360);
+        if rotate == 0 {
+            return Ok(None);
+        }
+
+        let original = image.to_dynamic_image()?;
+        let rotated = match rotate {
+            90 => original.rotate90(),
+            180 => original.rotate180(),
+            270 => original.rotate270(),
+            _ => {
+                return Err(TessError::ParseError(format!(
+                    "OSD rotation of {} degrees",
+                    rotate
+                )))
+            }
+        };
+
+        Ok(Some(Upright {
+            image: Image::from_dynamic_image_via_stdin(&rotated, StdinFormat::default())?,
+            rotate,
+            width: original.width() as i32,
+            height: original.height() as i32,
+        }))
+    }
+
+    /// Maps a box found in the upright image back to the original image.
+    pub fn unrotate(&self, bbox: BoundingBox) -> BoundingBox {
+        let (width, height) = (self.width, self.height);
+        match self.rotate {
+            90 => BoundingBox::new(
+                bbox.top,
+                height - bbox.right,
+                bbox.bottom,
+                height - bbox.left,
+            ),
+            180 => BoundingBox::new(
+                width - bbox.right,
+                height - bbox.bottom,
+                width - bbox.left,
+                height - bbox.top,
+            ),
+            270 => BoundingBox::new(width - bbox.bottom, bbox.left, width - bbox.top, bbox.right),
+            _ => bbox,
+        }
+    }
+
+    /// Height of the upright image.
+    fn rotated_height(&self) -> i32 {
+        match self.rotate {
+            90 | 270 => self.width,
+            _ => self.height,
+        }
+    }
+}
+
+impl Tesseract {
+    /// Runs OSD when `args.auto_rotate` is set and returns the upright image
+    /// if the input turned out to be rotated.
+    pub(crate) fn upright(&self, image: &Image, args: &Args) -> TessResult<Option<Upright>> {
+        if !args.auto_rotate {
+            return Ok(None);
+        }
+        check_osd_installed(&self.get_tesseract_langs()?)?;
+        let mut command = self.create_osd_command(image, args)?;
+
+        let output = run_tesseract_command(&mut command, RunOptions::new(image, args));
+        Upright::new(image, detected_rotation(output)?)
+    }
+
+    #[cfg(feature = "tokio")]
+    pub(crate) async fn upright_async(
+        &self,
+        image: &Image,
+        args: &Args,
+    ) -> TessResult<Option<Upright>> {
+        if !args.auto_rotate {
+            return Ok(None);
+        }
+        check_osd_installed(&self.get_tesseract_langs_async().await?)?;
+        let command = self.create_osd_command(image, args)?;
+
+        let output = run_tesseract_command_async(command, RunOptions::new(image, args)).await;
+        Upright::new(image, detected_rotation(output)?)
+    }
+}
+
+/// Rotation reported by OSD. Pages with too little text for OSD are
+/// recognized as they are.
+fn detected_rotation(output: TessResult<ProcessOutput>) -> TessResult<i32> {
+    let too_few_characters = |x: &[Diagnostic]| x.contains(&Diagnostic::TooFewCharacters);
+    match output {
+        Ok(output) if too_few_characters(&output.diagnostics) => Ok(0),
+        Ok(output) => Ok(OsdOutput::from_output(output)?.rotate),
+        Err(TessError::ExitCodeError { stderr, .. })
+            if too_few_characters(&parse_diagnostics(&stderr)) =>
+        {
+            Ok(0)
+        }
+        Err(error) => Err(error),
+    }
+}
+
+impl BoxOutput {
+    pub(crate) fn unrotate(&mut self, upright: &Upright) {
+        for x in &mut self.boxes {
+            let bbox = upright.unrotate(x.bbox(upright.rotated_height()));
+            x.set_bbox(bbox, upright.height);
+        }
+    }
+}
+
+impl DataOutput {
+    pub(crate) fn unrotate(&mut self, upright: &Upright) {
+        for x in &mut self.data {
+            x.set_bbox(upright.unrotate(x.bbox()));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Upright;
+    #[cfg(unix)]
+    use crate::tesseract::test_util::FakeTesseract;
+    use crate::*;
+    use image::DynamicImage;
+
+    fn upright(rotate: i32) -> Upright {
+        let original = DynamicImage::new_luma8(100, 50);
+        let image = Image::from_dynamic_image_via_stdin(&original, StdinFormat::Pnm).unwrap();
+        Upright::new(&image, rotate).unwrap().unwrap()
+    }
+
+    #[test]
+    fn test_unrotate() {
+        // a box in the top-left corner of the original image
+        let original = BoundingBox::new(0, 0, 10, 5);
+        assert_eq!(
+            upright(90).unrotate(BoundingBox::new(45, 0, 50, 10)),
+            original
+        );
+        assert_eq!(
+            upright(180).unrotate(BoundingBox::new(90, 45, 100, 50)),
+            original
+        );
+        assert_eq!(
+            upright(270).unrotate(BoundingBox::new(0, 90, 5, 100)),
+            original
+        );
+        assert!(
+            Upright::new(&Image::from_path("img/string.png").unwrap(), 0)
+                .unwrap()
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn test_unrotate_outputs() {
+        let upright = upright(90);
+
+        let mut boxes = BoxOutput {
+            output: "".into(),
+            boxes: vec![Box {
+                symbol: "L".into(),
+                left: 45,
+                bottom: 90,
+                right: 50,
+                top: 100,
+                page: 0,
+            }],
+            diagnostics: vec![],
+        };
+        boxes.unrotate(&upright);
+        let x = &boxes.boxes[0];
+        assert_eq!((x.left, x.bottom, x.right, x.top), (0, 45, 10, 50));
+
+        let mut data = DataOutput {
+            output: "".into(),
+            data: vec![Data {
+                level: 5,
+                page_num: 1,
+                block_num: 1,
+                par_num: 1,
+                line_num: 1,
+                word_num: 1,
+                left: 45,
+                top: 0,
+                width: 5,
+                height: 10,
+                conf: 90.0,
+                text: "L".into(),
+            }],
+            diagnostics: vec![],
+        };
+        data.unrotate(&upright);
+        let x = &data.data[0];
+        assert_eq!((x.left, x.top, x.width, x.height), (0, 0, 10, 5));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_image_to_data_auto_rotate() {
+        let fake = FakeTesseract::new(
+            r#"case "$*" in
+    *--list-langs*) printf 'List of available languages (2):\neng\nosd\n' ;;
+    *"--psm 0"*) printf 'Page number: 0\nOrientation in degrees: 270\nRotate: 90\nOrientation confidence: 9.58\nScript: Latin\nScript confidence: 2.25\n' ;;
+    *tsv) printf 'level\tpage_num\tblock_num\tpar_num\tline_num\tword_num\tleft\ttop\twidth\theight\tconf\ttext\n5\t1\t1\t1\t1\t1\t45\t0\t5\t10\t90\tL\n' ;;
+esac"#,
+        );
+        let original = DynamicImage::new_luma8(100, 50);
+        let image = Image::from_dynamic_image_via_stdin(&original, StdinFormat::Pnm).unwrap();
+        let args = Args {
+            auto_rotate: true,
+            ..Default::default()
+        };
+
+        let output = fake.tesseract.image_to_data(&image, &args).unwrap();
+        let x = &output.data[0];
+        assert_eq!((x.left, x.top, x.width, x.height), (0, 0, 10, 5));
+    }
+}
@@ -1,6 +1,10 @@
 use super::*;
-use std::process::{Command, Stdio};
+use std::io::{self, Read, Write};
+use std::process::{Command, Output, Stdio};
 use std::string::ToString;
+use std::sync::Arc;
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
 
 use crate::error::{TessError, TessResult};
 
@@ -10,33 +14,215 @@ use std::os::windows::process::CommandExt;
 #[cfg(target_os = "windows")]
 const CREATE_NO_WINDOW: u32 = 0x08000000;
 
-pub(crate) fn get_tesseract_command() -> Command {
-    let tesseract = if cfg!(target_os = "windows") {
-        "tesseract.exe"
-    } else {
-        "tesseract"
-    };
+impl Tesseract {
+    pub fn get_tesseract_version(&self) -> TessResult<String> {
+        Self::cached(&self.version, || {
+            let mut command = self.command();
+            command.arg("--version");
+
+            let output = run_tesseract_command(&mut command, RunOptions::default())?;
+            Ok(output.stdout)
+        })
+    }
+
+    pub fn get_tesseract_langs(&self) -> TessResult<Vec<String>> {
+        Self::cached(&self.langs, || {
+            let mut command = self.command();
+            command.arg("--list-langs");
+
+            let output = run_tesseract_command(&mut command, RunOptions::default())?;
+            Ok(parse_langs(&output.stdout))
+        })
+    }
+
+    pub(crate) fn create_tesseract_command(
+        &self,
+        image: &Image,
+        args: &Args,
+    ) -> TessResult<Command> {
+        self.create_tesseract_command_for_input(image.get_input_arg()?, args)
+    }
+
+    /// Builds the command for an input argument other than a single image,
+    /// such as a file listing several image paths.
+    pub(crate) fn create_tesseract_command_for_input(
+        &self,
+        input: &str,
+        args: &Args,
+    ) -> TessResult<Command> {
+        args.validate()?;
+
+        let mut command = self.command();
+        command
+            .arg(input)
+            .arg("stdout")
+            .arg("-l")
+            .arg(args.lang.clone())
+            .arg("--dpi")
+            .arg(args.dpi.to_string())
+            .arg("--psm")
+            .arg(args.psm.to_string())
+            .arg("--oem")
+            .arg(args.oem.to_string());
+
+        for parameter in args.get_config_variable_args() {
+            command.arg("-c").arg(parameter);
+        }
 
-    Command::new(tesseract)
+        Ok(command)
+    }
 }
 
 pub fn get_tesseract_version() -> TessResult<String> {
-    let mut command = get_tesseract_command();
-    command.arg("--version");
-
-    run_tesseract_command(&mut command)
+    Tesseract::shared().get_tesseract_version()
 }
 
 pub fn get_tesseract_langs() -> TessResult<Vec<String>> {
-    let mut command = get_tesseract_command();
-    command.arg("--list-langs");
+    Tesseract::shared().get_tesseract_langs()
+}
+
+pub(crate) const POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+/// Stdout of a successful tesseract run together with the parsed stderr.
+#[derive(Debug)]
+pub(crate) struct ProcessOutput<T = String> {
+    pub stdout: T,
+    pub diagnostics: Vec<Diagnostic>,
+}
+
+impl ProcessOutput<Vec<u8>> {
+    pub fn into_text(self) -> TessResult<ProcessOutput> {
+        let stdout = String::from_utf8(self.stdout)
+            .map_err(|e| TessError::OutputEncodingError(e.to_string()))?;
+
+        Ok(ProcessOutput {
+            stdout,
+            diagnostics: self.diagnostics,
+        })
+    }
+}
+
+/// Input and limits applied while running a tesseract process.
+#[derive(Clone, Default)]
+pub(crate) struct RunOptions<'a> {
+    pub stdin: Option<Arc<[u8]>>,
+    pub timeout: Option<Duration>,
+    pub cancellation: Option<&'a CancellationToken>,
+}
+
+impl<'a> RunOptions<'a> {
+    pub fn new(image: &Image, args: &'a Args) -> Self {
+        RunOptions {
+            stdin: image.get_stdin_data(),
+            ..Self::from_args(args)
+        }
+    }
+
+    /// Limits of `args` for a command that reads its input from files.
+    pub fn from_args(args: &'a Args) -> Self {
+        RunOptions {
+            stdin: None,
+            timeout: args.timeout,
+            cancellation: args.cancellation.as_ref(),
+        }
+    }
+}
+
+pub(crate) fn run_tesseract_command(
+    command: &mut Command,
+    options: RunOptions,
+) -> TessResult<ProcessOutput> {
+    run_tesseract_command_bytes(command, options)?.into_text()
+}
+
+/// Runs tesseract without decoding stdout, for binary renderers like `pdf`.
+pub(crate) fn run_tesseract_command_bytes(
+    command: &mut Command,
+    options: RunOptions,
+) -> TessResult<ProcessOutput<Vec<u8>>> {
+    prepare_tesseract_command(command, options.stdin.is_some());
+
+    let mut child = command.spawn().map_err(spawn_error)?;
+    if let (Some(stdin), Some(data)) = (child.stdin.take(), options.stdin) {
+        write_pipe(stdin, data);
+    }
 
-    let output = run_tesseract_command(&mut command)?;
-    let langs = output.lines().skip(1).map(|x| x.into()).collect();
-    Ok(langs)
+    if options.timeout.is_none() && options.cancellation.is_none() {
+        let output = child.wait_with_output().map_err(process_io_error)?;
+        return process_tesseract_output(output);
+    }
+
+    // drain the pipes while polling so a chatty child cannot block on a full pipe
+    let stdout = read_pipe(child.stdout.take());
+    let stderr = read_pipe(child.stderr.take());
+    let deadline = options.timeout.map(|timeout| Instant::now() + timeout);
+
+    let status = loop {
+        if let Some(status) = child.try_wait().map_err(process_io_error)? {
+            break status;
+        }
+
+        let error = if options.cancellation.is_some_and(|x| x.is_cancelled()) {
+            Some(TessError::CancelledError)
+        } else if deadline.is_some_and(|x| Instant::now() >= x) {
+            options.timeout.map(TessError::TimeoutError)
+        } else {
+            None
+        };
+
+        if let Some(error) = error {
+            // the reader threads are detached and finish once the pipes close
+            let _ = child.kill();
+            let _ = child.wait();
+            return Err(error);
+        }
+
+        thread::sleep(POLL_INTERVAL);
+    };
+
+    process_tesseract_output(Output {
+        status,
+        stdout: join_pipe(stdout)?,
+        stderr: join_pipe(stderr)?,
+    })
 }
 
-pub(crate) fn run_tesseract_command(command: &mut Command) -> TessResult<String> {
+fn read_pipe<R: Read + Send + 'static>(pipe: Option<R>) -> JoinHandle<io::Result<Vec<u8>>> {
+    thread::spawn(move || {
+        let mut buffer = Vec::new();
+        if let Some(mut pipe) = pipe {
+            pipe.read_to_end(&mut buffer)?;
+        }
+        Ok(buffer)
+    })
+}
+
+fn write_pipe<W: Write + Send + 'static>(mut pipe: W, data: Arc<[u8]>) {
+    // tesseract reports unreadable input through its exit status, so write errors are dropped
+    thread::spawn(move || {
+        let _ = pipe.write_all(&data);
+    });
+}
+
+fn join_pipe(reader: JoinHandle<io::Result<Vec<u8>>>) -> TessResult<Vec<u8>> {
+    reader
+        .join()
+        .unwrap_or_else(|_| Err(io::Error::other("pipe reader panicked")))
+        .map_err(process_io_error)
+}
+
+pub(crate) fn spawn_error(error: io::Error) -> TessError {
+    match error.kind() {
+        io::ErrorKind::NotFound => TessError::TesseractNotFoundError,
+        _ => TessError::SpawnError(error.into()),
+    }
+}
+
+pub(crate) fn process_io_error(error: io::Error) -> TessError {
+    TessError::ProcessIoError(error.into())
+}
+
+pub(crate) fn prepare_tesseract_command(command: &mut Command, stdin: bool) {
     if cfg!(debug_assertions) {
         show_command(command);
     }
@@ -44,24 +230,36 @@ pub(crate) fn run_tesseract_command(command: &mut Command) -> TessResult<String>
     #[cfg(target_os = "windows")]
     command.creation_flags(CREATE_NO_WINDOW);
 
-    let child = command
-        .stdout(Stdio::piped())
-        .stderr(Stdio::piped())
-        .spawn()
-        .map_err(|_| TessError::TesseractNotFoundError)?;
-
-    let output = child
-        .wait_with_output()
-        .map_err(|_| TessError::TesseractNotFoundError)?;
+    if stdin {
+        command.stdin(Stdio::piped());
+    }
+    command.stdout(Stdio::piped()).stderr(Stdio::piped());
+}
 
-    let out = String::from_utf8(output.stdout).unwrap();
-    let err = String::from_utf8(output.stderr).unwrap();
+pub(crate) fn process_tesseract_output(output: Output) -> TessResult<ProcessOutput<Vec<u8>>> {
+    // stderr only carries diagnostics, so a stray invalid byte must not hide the real result
+    let stderr = String::from_utf8_lossy(&output.stderr).into_owned();
     let status = output.status;
 
-    match status.code() {
-        Some(0) => Ok(out),
-        _ => Err(TessError::VersionError(err)),
+    if !status.success() {
+        #[cfg(unix)]
+        if let Some(signal) = std::os::unix::process::ExitStatusExt::signal(&status) {
+            return Err(TessError::SignalError { signal, stderr });
+        }
+        return Err(TessError::ExitCodeError {
+            code: status.code().unwrap_or(-1),
+            stderr,
+        });
     }
+
+    Ok(ProcessOutput {
+        stdout: output.stdout,
+        diagnostics: parse_diagnostics(&stderr),
+    })
+}
+
+pub(crate) fn parse_langs(output: &str) -> Vec<String> {
+    output.lines().skip(1).map(|x| x.into()).collect()
 }
 
 fn show_command(command: &Command) {
@@ -71,44 +269,20 @@ fn show_command(command: &Command) {
         .map(|s| s.to_string())
         .collect();
 
-    println!(
+    // stderr keeps stdout free for the recognized output
+    eprintln!(
         "Tesseract Command: {} {}",
         command.get_program().to_str().unwrap(),
         params.join(" ")
     );
 }
 
-pub fn image_to_string(image: &Image, args: &Args) -> TessResult<String> {
-    let mut command = create_tesseract_command(image, args)?;
-    let output = run_tesseract_command(&mut command)?;
-
-    Ok(output)
-}
-
-pub(crate) fn create_tesseract_command(image: &Image, args: &Args) -> TessResult<Command> {
-    let mut command = get_tesseract_command();
-    command
-        .arg(image.get_image_path()?)
-        .arg("stdout")
-        .arg("-l")
-        .arg(args.lang.clone())
-        .arg("--dpi")
-        .arg(args.dpi.to_string())
-        .arg("--psm")
-        .arg(args.psm.to_string())
-        .arg("--oem")
-        .arg(args.oem.to_string());
-
-    if let Some(parameter) = args.get_config_variable_args() {
-        command.arg("-c").arg(parameter);
-    }
-
-    Ok(command)
-}
-
 #[cfg(test)]
 mod tests {
+    #[cfg(unix)]
+    use crate::tesseract::test_util::FakeTesseract;
     use crate::*;
+    use std::time::{Duration, Instant};
 
     #[test]
     fn test_get_tesseract_langs() {
@@ -116,4 +290,95 @@ mod tests {
 
         assert!(langs.contains(&"eng".into()));
     }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_exit_code_error() {
+        let fake = FakeTesseract::new("echo 'Error opening data file' >&2\nexit 3");
+        assert_eq!(
+            fake.tesseract.get_tesseract_version(),
+            Err(TessError::ExitCodeError {
+                code: 3,
+                stderr: "Error opening data file\n".into()
+            })
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_signal_error() {
+        let fake = FakeTesseract::new("kill -9 $$");
+        let result = fake.tesseract.get_tesseract_version();
+        assert_eq!(
+            result,
+            Err(TessError::SignalError {
+                signal: 9,
+                stderr: "".into()
+            })
+        );
+        assert!(result.unwrap_err().is_transient());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_output_encoding_error() {
+        let fake = FakeTesseract::new("printf '\\377'");
+        assert!(matches!(
+            fake.tesseract.get_tesseract_version(),
+            Err(TessError::OutputEncodingError(_))
+        ));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_spawn_error() {
+        let dir = tempfile::tempdir().unwrap();
+        let tesseract = Tesseract::new(dir.path());
+        assert!(matches!(
+            tesseract.get_tesseract_version(),
+            Err(TessError::SpawnError(e)) if e.kind() == std::io::ErrorKind::PermissionDenied
+        ));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_timeout() {
+        let fake = FakeTesseract::new("exec sleep 10");
+        let img = Image::from_path("img/string.png").unwrap();
+        let args = Args {
+            timeout: Some(Duration::from_millis(100)),
+            ..Default::default()
+        };
+
+        let started = Instant::now();
+        let result = fake.tesseract.image_to_string(&img, &args);
+        assert_eq!(
+            result,
+            Err(TessError::TimeoutError(Duration::from_millis(100)))
+        );
+        assert!(started.elapsed() < Duration::from_secs(5));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_cancellation() {
+        let fake = FakeTesseract::new("exec sleep 10");
+        let img = Image::from_path("img/string.png").unwrap();
+        let token = CancellationToken::new();
+        let args = Args {
+            cancellation: Some(token.clone()),
+            ..Default::default()
+        };
+
+        let canceller = std::thread::spawn(move || {
+            std::thread::sleep(Duration::from_millis(100));
+            token.cancel();
+        });
+
+        let started = Instant::now();
+        let result = fake.tesseract.image_to_string(&img, &args);
+        canceller.join().unwrap();
+        assert_eq!(result, Err(TessError::CancelledError));
+        assert!(started.elapsed() < Duration::from_secs(5));
+    }
 }
@@ -0,0 +1,186 @@
+use core::fmt;
+
+/// Value of a tesseract config variable passed with `-c`.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(untagged))]
+pub enum ConfigValue {
+    Bool(bool),
+    Int(i64),
+    Double(f64),
+    String(String),
+}
+
+/// Type of a config variable's value, used to check values before they are
+/// passed to tesseract.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum ConfigValueType {
+    Bool,
+    Int,
+    Double,
+    String,
+}
+
+impl ConfigValue {
+    /// Infers the most specific value from its textual form. Only `true` and
+    /// `false` give a `Bool`, since tesseract prints booleans as `0`/`1`.
+    pub fn infer(value: &str) -> Self {
+        if value.eq_ignore_ascii_case("true") || value.eq_ignore_ascii_case("false") {
+            ConfigValue::Bool(value.eq_ignore_ascii_case("true"))
+        } else if let Ok(x) = value.parse::<i64>() {
+            ConfigValue::Int(x)
+        } else if let Some(x) = value.parse::<f64>().ok().filter(|x| x.is_finite()) {
+            ConfigValue::Double(x)
+        } else {
+            ConfigValue::String(value.into())
+        }
+    }
+
+    /// Whether both values mean the same to tesseract, e.g. `Bool(true)` and
+    /// `Int(1)` or `Int(2)` and `Double(2.0)`.
+    pub fn same_as(&self, other: &ConfigValue) -> bool {
+        let normalize = |x: &ConfigValue| match ConfigValue::infer(&x.to_string()) {
+            ConfigValue::Bool(x) => ConfigValue::Int(x.into()),
+            x => x,
+        };
+        match (normalize(self), normalize(other)) {
+            (ConfigValue::Int(a), ConfigValue::Double(b))
+            | (ConfigValue::Double(b), ConfigValue::Int(a)) => a as f64 == b,
+            (a, b) => a == b,
+        }
+    }
+
+    pub fn value_type(&self) -> ConfigValueType {
+        match self {
+            ConfigValue::Bool(_) => ConfigValueType::Bool,
+            ConfigValue::Int(_) => ConfigValueType::Int,
+            ConfigValue::Double(_) => ConfigValueType::Double,
+            ConfigValue::String(_) => ConfigValueType::String,
+        }
+    }
+}
+
+impl ConfigValueType {
+    /// Whether a variable of this type can be set to `value`, judged by the
+    /// text passed to tesseract. Booleans take `0`, `1`, `true` or `false`,
+    /// integers take whole numbers and doubles any finite number.
+    pub fn accepts(self, value: &ConfigValue) -> bool {
+        let text = match value {
+            ConfigValue::String(x) => x.trim().to_string(),
+            x => x.to_string(),
+        };
+        match self {
+            ConfigValueType::String => true,
+            ConfigValueType::Bool => {
+                ["0", "1", "true", "false"].contains(&text.to_lowercase().as_str())
+            }
+            ConfigValueType::Int => text.parse::<i64>().is_ok(),
+            ConfigValueType::Double => text.parse::<f64>().is_ok_and(|x| x.is_finite()),
+        }
+    }
+}
+
+impl fmt::Display for ConfigValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConfigValue::Bool(x) => write!(f, "{}", u8::from(*x)),
+            ConfigValue::Int(x) => write!(f, "{}", x),
+            ConfigValue::Double(x) => write!(f, "{}", x),
+            ConfigValue::String(x) => write!(f, "{}", x),
+        }
+    }
+}
+
+impl From<bool> for ConfigValue {
+    fn from(value: bool) -> Self {
+        ConfigValue::Bool(value)
+    }
+}
+
+impl From<i32> for ConfigValue {
+    fn from(value: i32) -> Self {
+        ConfigValue::Int(value.into())
+    }
+}
+
+impl From<i64> for ConfigValue {
+    fn from(value: i64) -> Self {
+        ConfigValue::Int(value)
+    }
+}
+
+impl From<f64> for ConfigValue {
+    fn from(value: f64) -> Self {
+        ConfigValue::Double(value)
+    }
+}
+
+impl From<&str> for ConfigValue {
+    fn from(value: &str) -> Self {
+        ConfigValue::String(value.into())
+    }
+}
+
+impl From<String> for ConfigValue {
+    fn from(value: String) -> Self {
+        ConfigValue::String(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_infer() {
+        assert_eq!(
+            ConfigValue::infer("2147483647"),
+            ConfigValue::Int(2147483647)
+        );
+        assert_eq!(ConfigValue::infer("0.5"), ConfigValue::Double(0.5));
+        assert_eq!(ConfigValue::infer("True"), ConfigValue::Bool(true));
+        assert_eq!(ConfigValue::infer("1"), ConfigValue::Int(1));
+        assert_eq!(ConfigValue::infer(""), ConfigValue::String("".into()));
+        assert_eq!(ConfigValue::infer("eng"), ConfigValue::String("eng".into()));
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(ConfigValue::from(true).to_string(), "1");
+        assert_eq!(ConfigValue::from(0.25).to_string(), "0.25");
+        assert_eq!(ConfigValue::from("abc").to_string(), "abc");
+    }
+
+    #[test]
+    fn test_same_as() {
+        assert!(ConfigValue::Bool(true).same_as(&ConfigValue::Int(1)));
+        assert!(ConfigValue::from("false").same_as(&ConfigValue::Int(0)));
+        assert!(ConfigValue::Int(2).same_as(&ConfigValue::Double(2.0)));
+        assert!(ConfigValue::from("0.50").same_as(&ConfigValue::Double(0.5)));
+        assert!(!ConfigValue::from("abc").same_as(&ConfigValue::from("abd")));
+    }
+
+    #[test]
+    fn test_accepts() {
+        assert!(ConfigValueType::Int.accepts(&ConfigValue::Bool(true)));
+        assert!(ConfigValueType::Int.accepts(&ConfigValue::Double(2.0)));
+        assert!(ConfigValueType::Int.accepts(&"-7".into()));
+        assert!(!ConfigValueType::Int.accepts(&ConfigValue::Double(0.5)));
+        assert!(!ConfigValueType::Int.accepts(&"2.7".into()));
+        assert!(!ConfigValueType::Int.accepts(&"abc".into()));
+
+        assert!(ConfigValueType::Bool.accepts(&ConfigValue::Bool(false)));
+        assert!(ConfigValueType::Bool.accepts(&ConfigValue::Int(1)));
+        assert!(ConfigValueType::Bool.accepts(&"TRUE".into()));
+        assert!(!ConfigValueType::Bool.accepts(&ConfigValue::Int(2)));
+        assert!(!ConfigValueType::Bool.accepts(&ConfigValue::Double(0.5)));
+        assert!(!ConfigValueType::Bool.accepts(&"yes".into()));
+
+        assert!(ConfigValueType::Double.accepts(&"0.75".into()));
+        assert!(ConfigValueType::Double.accepts(&ConfigValue::Bool(true)));
+        assert!(!ConfigValueType::Double.accepts(&"inf".into()));
+        assert!(ConfigValueType::String.accepts(&ConfigValue::Int(3)));
+    }
+}
@@ -0,0 +1,137 @@
+use super::*;
+use polars::prelude::*;
+
+impl DataOutput {
+    /// One row per TSV line. `level` is categorical with the names of
+    /// [`Level`], and `conf` is null where tesseract reports -1.
+    pub fn to_dataframe(&self) -> PolarsResult<DataFrame> {
+        let int_column = |name: &str, value: fn(&Data) -> i32| {
+            Column::new(name.into(), self.data.iter().map(value).collect::<Vec<_>>())
+        };
+        let level = Column::new(
+            "level".into(),
+            self.data
+                .iter()
+                .map(|x| Level::try_from(x.level).ok().map(|x| x.to_string()))
+                .collect::<Vec<_>>(),
+        )
+        .cast(&DataType::Categorical(None, CategoricalOrdering::Physical))?;
+
+        DataFrame::new(vec![
+            level,
+            int_column("page_num", |x| x.page_num),
+            int_column("block_num", |x| x.block_num),
+            int_column("par_num", |x| x.par_num),
+            int_column("line_num", |x| x.line_num),
+            int_column("word_num", |x| x.word_num),
+            int_column("left", |x| x.left),
+            int_column("top", |x| x.top),
+            int_column("width", |x| x.width),
+            int_column("height", |x| x.height),
+            Column::new(
+                "conf".into(),
+                self.data
+                    .iter()
+                    .map(|x| (x.conf >= 0.0).then_some(x.conf))
+                    .collect::<Vec<_>>(),
+            ),
+            Column::new(
+                "text".into(),
+                self.data
+                    .iter()
+                    .map(|x| x.text.as_str())
+                    .collect::<Vec<_>>(),
+            ),
+        ])
+    }
+}
+
+impl BoxOutput {
+    /// One row per symbol with the box file's bottom-left coordinates.
+    pub fn to_dataframe(&self) -> PolarsResult<DataFrame> {
+        let int_column = |name: &str, value: fn(&Box) -> i32| {
+            Column::new(
+                name.into(),
+                self.boxes.iter().map(value).collect::<Vec<_>>(),
+            )
+        };
+
+        DataFrame::new(vec![
+            Column::new(
+                "symbol".into(),
+                self.boxes
+                    .iter()
+                    .map(|x| x.symbol.as_str())
+                    .collect::<Vec<_>>(),
+            ),
+            int_column("left", |x| x.left),
+            int_column("bottom", |x| x.bottom),
+            int_column("right", |x| x.right),
+            int_column("top", |x| x.top),
+            int_column("page", |x| x.page),
+        ])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tesseract::test_util::{box_output, data_output};
+    use crate::*;
+    use polars::prelude::*;
+
+    #[test]
+    fn test_data_output_to_dataframe() {
+        let output = data_output(
+            "1\t1\t0\t0\t0\t0\t0\t0\t696\t89\t-1\t\n\
+            5\t1\t1\t1\t1\t1\t18\t29\t144\t35\t95.5\tLOREM\n",
+        );
+        let df = output.to_dataframe().unwrap();
+
+        assert_eq!(df.shape(), (2, 12));
+        assert!(matches!(
+            df.column("level").unwrap().dtype(),
+            DataType::Categorical(..)
+        ));
+        assert_eq!(
+            df.column("level")
+                .unwrap()
+                .cast(&DataType::String)
+                .unwrap()
+                .str()
+                .unwrap()
+                .into_iter()
+                .collect::<Vec<_>>(),
+            vec![Some("page"), Some("word")]
+        );
+        assert_eq!(
+            df.column("conf")
+                .unwrap()
+                .f32()
+                .unwrap()
+                .into_iter()
+                .collect::<Vec<_>>(),
+            vec![None, Some(95.5)]
+        );
+        assert_eq!(df.column("width").unwrap().i32().unwrap().get(1), Some(144));
+        assert_eq!(
+            df.column("text").unwrap().str().unwrap().get(1),
+            Some("LOREM")
+        );
+    }
+
+    #[test]
+    fn test_box_output_to_dataframe() {
+        let output = box_output("L 18 26 36 59 0\nO 35 25 70 60 0");
+        let df = output.to_dataframe().unwrap();
+
+        assert_eq!(
+            df.get_column_names(),
+            vec!["symbol", "left", "bottom", "right", "top", "page"]
+        );
+        assert_eq!(
+            df.column("symbol").unwrap().str().unwrap().get(1),
+            Some("O")
+        );
+        assert_eq!(df.column("top").unwrap().i32().unwrap().get(0), Some(59));
+    }
+}
@@ -0,0 +1,140 @@
+use core::fmt;
+
+/// Message tesseract wrote to stderr during a successful run.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum Diagnostic {
+    /// `Warning: Invalid resolution 0 dpi. Using 70 instead.`
+    InvalidResolution {
+        given: i32,
+        used: Option<i32>,
+    },
+    /// `Estimating resolution as 284`
+    EstimatedResolution(i32),
+    /// `Empty page!!`
+    EmptyPage,
+    /// `Too few characters. Skipping this page`
+    TooFewCharacters,
+    /// `Image too small to scale!! (2x36 vs min width of 3)`
+    ImageTooSmall,
+    /// `Line cannot be recognized!!`
+    LineNotRecognized,
+    Other(String),
+}
+
+impl Diagnostic {
+    /// Whether the message indicates that the recognized text is likely
+    /// incomplete or empty.
+    pub fn is_suspicious(&self) -> bool {
+        matches!(
+            self,
+            Diagnostic::EmptyPage
+                | Diagnostic::TooFewCharacters
+                | Diagnostic::ImageTooSmall
+                | Diagnostic::LineNotRecognized
+        )
+    }
+
+    fn from_line(line: &str) -> Self {
+        if let Some(rest) = line
+            .strip_prefix("Warning: Invalid resolution ")
+            .or_else(|| line.strip_prefix("Warning. Invalid resolution "))
+        {
+            let mut numbers = rest
+                .split(|c: char| !c.is_ascii_digit())
+                .filter(|x| !x.is_empty())
+                .map(|x| x.parse().ok());
+            if let Some(Some(given)) = numbers.next() {
+                return Diagnostic::InvalidResolution {
+                    given,
+                    used: numbers.next().flatten(),
+                };
+            }
+        }
+        if let Some(Ok(dpi)) = line
+            .strip_prefix("Estimating resolution as ")
+            .map(|x| x.trim().parse())
+        {
+            return Diagnostic::EstimatedResolution(dpi);
+        }
+        if line.starts_with("Empty page!") {
+            return Diagnostic::EmptyPage;
+        }
+        if line.starts_with("Too few characters") {
+            return Diagnostic::TooFewCharacters;
+        }
+        if line.starts_with("Image too small to scale!") {
+            return Diagnostic::ImageTooSmall;
+        }
+        if line.starts_with("Line cannot be recognized!") {
+            return Diagnostic::LineNotRecognized;
+        }
+        Diagnostic::Other(line.into())
+    }
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Diagnostic::InvalidResolution {
+                given,
+                used: Some(used),
+            } => write!(
+                f,
+                "Warning: Invalid resolution {} dpi. Using {} instead.",
+                given, used
+            ),
+            Diagnostic::InvalidResolution { given, used: None } => {
+                write!(f, "Warning: Invalid resolution {} dpi.", given)
+            }
+            Diagnostic::EstimatedResolution(dpi) => write!(f, "Estimating resolution as {}", dpi),
+            Diagnostic::EmptyPage => write!(f, "Empty page!!"),
+            Diagnostic::TooFewCharacters => write!(f, "Too few characters. Skipping this page"),
+            Diagnostic::ImageTooSmall => write!(f, "Image too small to scale!!"),
+            Diagnostic::LineNotRecognized => write!(f, "Line cannot be recognized!!"),
+            Diagnostic::Other(line) => write!(f, "{}", line),
+        }
+    }
+}
+
+pub(crate) fn parse_diagnostics(stderr: &str) -> Vec<Diagnostic> {
+    stderr
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(Diagnostic::from_line)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_diagnostics() {
+        let result = parse_diagnostics(
+            "Warning: Invalid resolution 0 dpi. Using 70 instead.\n\
+            Estimating resolution as 284\n\
+            Empty page!!\n\
+            \n\
+            Too few characters. Skipping this page\n\
+            Detected 11 diacritics",
+        );
+        assert_eq!(
+            result,
+            vec![
+                Diagnostic::InvalidResolution {
+                    given: 0,
+                    used: Some(70)
+                },
+                Diagnostic::EstimatedResolution(284),
+                Diagnostic::EmptyPage,
+                Diagnostic::TooFewCharacters,
+                Diagnostic::Other("Detected 11 diacritics".into()),
+            ]
+        );
+        assert!(result[2].is_suspicious());
+        assert!(!result[1].is_suspicious());
+    }
+}
@@ -0,0 +1,292 @@
+use super::*;
+use core::fmt;
+
+/// Hierarchy level of a TSV row, from the `level` column.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum Level {
+    Page = 1,
+    Block = 2,
+    Paragraph = 3,
+    Line = 4,
+    Word = 5,
+}
+
+impl Level {
+    pub const ALL: [Level; 5] = [
+        Level::Page,
+        Level::Block,
+        Level::Paragraph,
+        Level::Line,
+        Level::Word,
+    ];
+
+    pub fn value(&self) -> i32 {
+        *self as i32
+    }
+
+    /// Separator between the texts of the children of a node at this level.
+    fn separator(&self) -> &'static str {
+        match self {
+            Level::Page | Level::Block => "\n\n",
+            Level::Paragraph => "\n",
+            Level::Line | Level::Word => " ",
+        }
+    }
+}
+
+impl TryFrom<i32> for Level {
+    type Error = TessError;
+
+    fn try_from(value: i32) -> Result<Self, Self::Error> {
+        Level::ALL
+            .into_iter()
+            .find(|x| x.value() == value)
+            .ok_or_else(|| TessError::ParseError(format!("TSV level {}", value)))
+    }
+}
+
+impl fmt::Display for Level {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Level::Page => "page",
+            Level::Block => "block",
+            Level::Paragraph => "paragraph",
+            Level::Line => "line",
+            Level::Word => "word",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// Pages, blocks, paragraphs, lines and words of a [`DataOutput`] as a tree.
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Document {
+    pub pages: Vec<DocumentNode>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DocumentNode {
+    pub level: Level,
+    /// Number of the node within its parent, e.g. `word_num` for words.
+    pub number: i32,
+    pub bbox: BoundingBox,
+    /// Recognition confidence from 0 to 100, `None` where tesseract reports -1.
+    pub confidence: Option<f32>,
+    /// Recognized text of a word, empty for all other levels.
+    pub text: String,
+    pub children: Vec<DocumentNode>,
+}
+
+impl Document {
+    /// Builds the tree from rows in tesseract's order, where every row follows
+    /// its parent.
+    pub fn from_data(data: &[Data]) -> TessResult<Self> {
+        let mut pages = Vec::new();
+        // path from the current page down to the most recent node
+        let mut path: Vec<DocumentNode> = Vec::new();
+
+        for row in data {
+            let node = DocumentNode::from_data(row)?;
+            while path.last().is_some_and(|x| x.level >= node.level) {
+                close(&mut path, &mut pages);
+            }
+            if path.is_empty() && node.level != Level::Page {
+                return Err(TessError::ParseError(format!(
+                    "TSV {} {} outside of a page",
+                    node.level, node.number
+                )));
+            }
+            path.push(node);
+        }
+        while !path.is_empty() {
+            close(&mut path, &mut pages);
+        }
+
+        Ok(Document { pages })
+    }
+
+    pub fn blocks(&self) -> Descendants<'_> {
+        Descendants::new(&self.pages, Level::Block)
+    }
+
+    pub fn paragraphs(&self) -> Descendants<'_> {
+        Descendants::new(&self.pages, Level::Paragraph)
+    }
+
+    pub fn lines(&self) -> Descendants<'_> {
+        Descendants::new(&self.pages, Level::Line)
+    }
+
+    pub fn words(&self) -> Descendants<'_> {
+        Descendants::new(&self.pages, Level::Word)
+    }
+
+    /// Text of all pages, separated by form feeds like tesseract's text output.
+    pub fn to_text(&self) -> String {
+        self.pages
+            .iter()
+            .map(|x| x.to_text())
+            .collect::<Vec<_>>()
+            .join("\n\x0c")
+    }
+}
+
+/// Moves the last node of `path` into its parent, or into `pages` for a page.
+fn close(path: &mut Vec<DocumentNode>, pages: &mut Vec<DocumentNode>) {
+    if let Some(node) = path.pop() {
+        match path.last_mut() {
+            Some(parent) => parent.children.push(node),
+            None => pages.push(node),
+        }
+    }
+}
+
+impl DocumentNode {
+    fn from_data(data: &Data) -> TessResult<Self> {
+        let level = Level::try_from(data.level)?;
+        let number = match level {
+            Level::Page => data.page_num,
+            Level::Block => data.block_num,
+            Level::Paragraph => data.par_num,
+            Level::Line => data.line_num,
+            Level::Word => data.word_num,
+        };
+
+        Ok(DocumentNode {
+            level,
+            number,
+            bbox: data.bbox(),
+            confidence: (data.conf >= 0.0).then_some(data.conf),
+            text: data.text.clone(),
+            children: Vec::new(),
+        })
+    }
+
+    /// Nodes of the given level below this one, in reading order.
+    pub fn descendants(&self, level: Level) -> Descendants<'_> {
+        Descendants::new(&self.children, level)
+    }
+
+    pub fn words(&self) -> Descendants<'_> {
+        self.descendants(Level::Word)
+    }
+
+    /// Words joined by spaces, lines by newlines and paragraphs and blocks by
+    /// blank lines. Empty words are skipped.
+    pub fn to_text(&self) -> String {
+        if self.level == Level::Word {
+            return self.text.clone();
+        }
+        self.children
+            .iter()
+            .map(|x| x.to_text())
+            .filter(|x| !x.is_empty())
+            .collect::<Vec<_>>()
+            .join(self.level.separator())
+    }
+}
+
+/// Depth-first iterator over the nodes of one level.
+pub struct Descendants<'a> {
+    stack: Vec<&'a DocumentNode>,
+    level: Level,
+}
+
+impl<'a> Descendants<'a> {
+    fn new(nodes: &'a [DocumentNode], level: Level) -> Self {
+        Descendants {
+            stack: nodes.iter().rev().collect(),
+            level,
+        }
+    }
+}
+
+impl<'a> Iterator for Descendants<'a> {
+    type Item = &'a DocumentNode;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some(node) = self.stack.pop() {
+            if node.level == self.level {
+                return Some(node);
+            }
+            if node.level < self.level {
+                self.stack.extend(node.children.iter().rev());
+            }
+        }
+        None
+    }
+}
+
+impl DataOutput {
+    pub fn to_document(&self) -> TessResult<Document> {
+        Document::from_data(&self.data)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tesseract::test_util;
+    use crate::*;
+
+    fn data_output() -> DataOutput {
+        test_util::data_output(
+            "1\t1\t0\t0\t0\t0\t0\t0\t696\t89\t-1\t\n\
+                2\t1\t1\t0\t0\t0\t18\t29\t653\t35\t-1\t\n\
+                3\t1\t1\t1\t0\t0\t18\t29\t653\t35\t-1\t\n\
+                4\t1\t1\t1\t1\t0\t18\t29\t653\t35\t-1\t\n\
+                5\t1\t1\t1\t1\t1\t18\t29\t144\t35\t95.5\tLOREM\n\
+                5\t1\t1\t1\t1\t2\t181\t29\t123\t35\t92.306282\tIPSUM\n\
+                4\t1\t1\t1\t2\t0\t18\t70\t100\t15\t-1\t\n\
+                5\t1\t1\t1\t2\t1\t18\t70\t100\t15\t90.531677\tDOLOR\n\
+                2\t1\t2\t0\t0\t0\t18\t90\t100\t15\t-1\t\n\
+                3\t1\t2\t1\t0\t0\t18\t90\t100\t15\t-1\t\n\
+                4\t1\t2\t1\t1\t0\t18\t90\t100\t15\t-1\t\n\
+                5\t1\t2\t1\t1\t1\t18\t90\t100\t15\t95.873787\tSIT\n",
+        )
+    }
+
+    #[test]
+    fn test_document_from_data() {
+        let document = data_output().to_document().unwrap();
+
+        assert_eq!(document.pages.len(), 1);
+        assert_eq!(document.blocks().count(), 2);
+        assert_eq!(document.paragraphs().count(), 2);
+        assert_eq!(document.lines().count(), 3);
+        assert_eq!(
+            document
+                .words()
+                .map(|x| x.text.as_str())
+                .collect::<Vec<_>>(),
+            vec!["LOREM", "IPSUM", "DOLOR", "SIT"]
+        );
+
+        let line = document.lines().next().unwrap();
+        assert_eq!(line.confidence, None);
+        assert_eq!(line.bbox, BoundingBox::new(18, 29, 671, 64));
+        assert_eq!(line.words().next().unwrap().confidence, Some(95.5));
+
+        assert_eq!(document.to_text(), "LOREM IPSUM\nDOLOR\n\nSIT");
+    }
+
+    #[test]
+    fn test_document_errors() {
+        assert_eq!(
+            Level::try_from(6),
+            Err(TessError::ParseError("TSV level 6".into()))
+        );
+
+        let mut output = data_output();
+        output.data.remove(0);
+        assert_eq!(
+            output.to_document(),
+            Err(TessError::ParseError(
+                "TSV block 1 outside of a page".into()
+            ))
+        );
+    }
+}
@@ -0,0 +1,148 @@
+use super::*;
+use std::env;
+use std::ffi::OsStr;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::OnceLock;
+
+#[cfg(target_os = "windows")]
+const DEFAULT_EXECUTABLE: &str = "tesseract.exe";
+
+#[cfg(not(target_os = "windows"))]
+const DEFAULT_EXECUTABLE: &str = "tesseract";
+
+/// Handle to a tesseract executable.
+///
+/// Version, language list and config parameters are queried once and cached
+/// for the lifetime of the handle.
+#[derive(Clone, Debug)]
+pub struct Tesseract {
+    executable: PathBuf,
+    pub(crate) version: OnceLock<String>,
+    pub(crate) langs: OnceLock<Vec<String>>,
+    pub(crate) config_parameters: OnceLock<ConfigParameterOutput>,
+}
+
+impl Tesseract {
+    /// Environment variable consulted by [`Tesseract::from_env`].
+    pub const ENV_VAR: &'static str = "TESSERACT_CMD";
+
+    /// Uses the tesseract executable at the given location.
+    pub fn new<P: Into<PathBuf>>(executable: P) -> Self {
+        Self {
+            executable: executable.into(),
+            version: OnceLock::new(),
+            langs: OnceLock::new(),
+            config_parameters: OnceLock::new(),
+        }
+    }
+
+    /// Uses the executable named by the `TESSERACT_CMD` environment variable, if set.
+    pub fn from_env() -> Option<Self> {
+        Self::from_env_var(Self::ENV_VAR)
+    }
+
+    /// Searches the directories in `PATH` for the tesseract executable.
+    pub fn from_path_lookup() -> TessResult<Self> {
+        let paths = env::var_os("PATH").ok_or(TessError::TesseractNotFoundError)?;
+        Self::lookup_in(&paths)
+    }
+
+    pub fn executable(&self) -> &Path {
+        &self.executable
+    }
+
+    fn from_env_var(key: &str) -> Option<Self> {
+        env::var_os(key)
+            .filter(|value| !value.is_empty())
+            .map(Self::new)
+    }
+
+    fn lookup_in(paths: &OsStr) -> TessResult<Self> {
+        env::split_paths(paths)
+            .map(|dir| dir.join(DEFAULT_EXECUTABLE))
+            .find(|candidate| candidate.is_file())
+            .map(Self::new)
+            .ok_or(TessError::TesseractNotFoundError)
+    }
+
+    /// Default handle behind the free functions, so that they share the
+    /// cached version, languages and config parameters. `TESSERACT_CMD` is
+    /// read on first use.
+    pub(crate) fn shared() -> &'static Self {
+        static SHARED: OnceLock<Tesseract> = OnceLock::new();
+        SHARED.get_or_init(Self::default)
+    }
+
+    pub(crate) fn command(&self) -> Command {
+        Command::new(&self.executable)
+    }
+
+    pub(crate) fn cached<T: Clone>(
+        cell: &OnceLock<T>,
+        query: impl FnOnce() -> TessResult<T>,
+    ) -> TessResult<T> {
+        if let Some(value) = cell.get() {
+            return Ok(value.clone());
+        }
+        let value = query()?;
+        Ok(cell.get_or_init(|| value).clone())
+    }
+}
+
+impl Default for Tesseract {
+    /// Prefers `TESSERACT_CMD` and otherwise lets the operating system resolve
+    /// `tesseract` through `PATH`.
+    fn default() -> Self {
+        Self::from_env().unwrap_or_else(|| Self::new(DEFAULT_EXECUTABLE))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+    use std::ffi::OsString;
+
+    #[test]
+    fn test_from_env_var() {
+        std::env::set_var("RUSTY_TESSERACT_TEST_CMD", "/opt/ocr/bin/tesseract");
+        let tesseract = Tesseract::from_env_var("RUSTY_TESSERACT_TEST_CMD").unwrap();
+        assert_eq!(
+            tesseract.executable(),
+            std::path::Path::new("/opt/ocr/bin/tesseract")
+        );
+
+        assert!(Tesseract::from_env_var("RUSTY_TESSERACT_TEST_UNSET").is_none());
+    }
+
+    #[test]
+    fn test_shared() {
+        assert!(std::ptr::eq(Tesseract::shared(), Tesseract::shared()));
+    }
+
+    #[test]
+    fn test_lookup_in() {
+        let dir = tempfile::tempdir().unwrap();
+        let executable = dir.path().join(super::DEFAULT_EXECUTABLE);
+        std::fs::write(&executable, "").unwrap();
+
+        let paths = std::env::join_paths([dir.path()]).unwrap();
+        assert_eq!(
+            Tesseract::lookup_in(&paths).unwrap().executable(),
+            executable
+        );
+        assert_eq!(
+            Tesseract::lookup_in(&OsString::new()).unwrap_err(),
+            TessError::TesseractNotFoundError
+        );
+    }
+
+    #[test]
+    fn test_missing_executable() {
+        let tesseract = Tesseract::new("/nonexistent/tesseract");
+        assert_eq!(
+            tesseract.get_tesseract_version(),
+            Err(TessError::TesseractNotFoundError)
+        );
+    }
+}
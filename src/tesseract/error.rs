@@ -1,30 +1,217 @@
+use std::{fmt, io, sync::Arc, time::Duration};
 use thiserror::Error;
 
-#[derive(Error, Debug, PartialEq)]
+use crate::TesseractVersion;
+
+#[derive(Error, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(tag = "kind", content = "details"))]
 pub enum TessError {
     #[error("Tesseract not found. Please check installation path!")]
     TesseractNotFoundError,
 
+    #[error("Could not start tesseract.\n{0}")]
+    SpawnError(#[source] IoError),
+
+    #[error("Could not communicate with the tesseract process.\n{0}")]
+    ProcessIoError(#[source] IoError),
+
+    #[error("Tesseract exited with code {code}.\n{stderr}")]
+    ExitCodeError { code: i32, stderr: String },
+
+    #[error("Tesseract was killed by signal {signal}.\n{stderr}")]
+    SignalError { signal: i32, stderr: String },
+
+    #[error("Tesseract output is not valid UTF-8.\n{0}")]
+    OutputEncodingError(String),
+
     #[error("Invalid Tesseract version!\n{0}")]
     VersionError(String),
 
+    #[error(
+        "Tesseract {found} does not support {feature}, version {required} or newer is required."
+    )]
+    UnsupportedVersionError {
+        feature: String,
+        required: TesseractVersion,
+        found: TesseractVersion,
+    },
+
+    #[error("Tesseract has no traineddata for '{0}', please check the installed languages.")]
+    MissingTraineddataError(String),
+
     #[error(
         "Image format not within the list of allowed image formats:\n\
-        ['JPEG','JPG','PNG','PBM','PGM','PPM','TIFF','BMP','GIF','WEBP']"
+        ['JPEG','PNG','GIF','BMP','TIFF','PNM','WEBP','JP2','SPIX','AVIF','QOI','ICO','HDR']"
     )]
     ImageFormatError,
 
     #[error("Please assign a valid image path.")]
     ImageNotFoundError,
 
+    #[error("Could not read image.\n{0}")]
+    ImageReadError(#[source] IoError),
+
+    #[error("Invalid arguments: {0}.")]
+    ArgumentError(String),
+
     #[error("Could not parse {0}.")]
     ParseError(String),
 
+    #[error("Output does not conform to its schema.\n{0}")]
+    SchemaError(String),
+
+    #[error("Could not parse line {line_number}: '{line}'.")]
+    LineParseError { line_number: usize, line: String },
+
     #[error("Could not create tempfile.\n{0}")]
     TempfileError(String),
 
     #[error("Could not save dynamic image to tempfile.\n{0}")]
     DynamicImageError(String),
+
+    #[error("Tesseract did not finish within {0:?}.")]
+    TimeoutError(
+        #[cfg_attr(
+            feature = "serde",
+            serde(with = "crate::tesseract::serde_util::seconds")
+        )]
+        Duration,
+    ),
+
+    #[error("Tesseract was cancelled.")]
+    CancelledError,
+
+    #[error("Could not access the watched directory.\n{0}")]
+    WatchError(#[source] IoError),
+}
+
+impl TessError {
+    /// Whether retrying the same call might succeed, e.g. after a timeout or
+    /// when the process was killed by the system.
+    pub fn is_transient(&self) -> bool {
+        match self {
+            TessError::SpawnError(error) => matches!(
+                error.kind(),
+                io::ErrorKind::Interrupted
+                    | io::ErrorKind::WouldBlock
+                    | io::ErrorKind::TimedOut
+                    | io::ErrorKind::OutOfMemory
+            ),
+            TessError::ProcessIoError(_)
+            | TessError::SignalError { .. }
+            | TessError::TimeoutError(_) => true,
+            _ => false,
+        }
+    }
+
+    /// Whether the failure comes from the installation or the system rather
+    /// than the input, e.g. a missing tesseract or traineddata, so the same
+    /// call may succeed once that is fixed.
+    pub fn is_environment_error(&self) -> bool {
+        match self {
+            TessError::ExitCodeError { stderr, .. } => {
+                stderr.contains("Failed loading language")
+                    || stderr.contains("Error opening data file")
+            }
+            TessError::TesseractNotFoundError
+            | TessError::SpawnError(_)
+            | TessError::VersionError(_)
+            | TessError::UnsupportedVersionError { .. }
+            | TessError::MissingTraineddataError(_)
+            | TessError::ImageReadError(_)
+            | TessError::TempfileError(_)
+            | TessError::WatchError(_) => true,
+            _ => false,
+        }
+    }
 }
 
 pub type TessResult<T> = Result<T, TessError>;
+
+/// Cloneable [`io::Error`], compared by kind and message.
+#[derive(Clone, Debug)]
+pub struct IoError(Arc<io::Error>);
+
+impl IoError {
+    pub fn kind(&self) -> io::ErrorKind {
+        self.0.kind()
+    }
+}
+
+impl AsRef<io::Error> for IoError {
+    fn as_ref(&self) -> &io::Error {
+        &self.0
+    }
+}
+
+impl From<io::Error> for IoError {
+    fn from(error: io::Error) -> Self {
+        IoError(Arc::new(error))
+    }
+}
+
+impl PartialEq for IoError {
+    fn eq(&self, other: &Self) -> bool {
+        self.kind() == other.kind() && self.0.to_string() == other.0.to_string()
+    }
+}
+
+impl fmt::Display for IoError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl std::error::Error for IoError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        self.0.source()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_transient() {
+        assert!(TessError::TimeoutError(Duration::from_secs(1)).is_transient());
+        assert!(TessError::SignalError {
+            signal: 9,
+            stderr: "".into()
+        }
+        .is_transient());
+        assert!(
+            TessError::SpawnError(io::Error::from(io::ErrorKind::Interrupted).into())
+                .is_transient()
+        );
+        assert!(
+            !TessError::SpawnError(io::Error::from(io::ErrorKind::PermissionDenied).into())
+                .is_transient()
+        );
+        assert!(!TessError::ExitCodeError {
+            code: 1,
+            stderr: "".into()
+        }
+        .is_transient());
+        assert!(!TessError::CancelledError.is_transient());
+    }
+
+    #[test]
+    fn test_is_environment_error() {
+        assert!(TessError::TesseractNotFoundError.is_environment_error());
+        assert!(TessError::MissingTraineddataError("deu".into()).is_environment_error());
+        assert!(TessError::ExitCodeError {
+            code: 1,
+            stderr: "Failed loading language 'deu'\nTesseract couldn't load any languages!".into()
+        }
+        .is_environment_error());
+        assert!(!TessError::ExitCodeError {
+            code: 1,
+            stderr: "Error in pixReadStream".into()
+        }
+        .is_environment_error());
+        assert!(!TessError::ImageFormatError.is_environment_error());
+        assert!(!TessError::ParseError("TSV".into()).is_environment_error());
+    }
+}
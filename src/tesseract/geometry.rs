@@ -0,0 +1,281 @@
+use core::fmt;
+
+/// Axis-aligned rectangle in pixels with a top-left origin. `right` and
+/// `bottom` are exclusive, matching tesseract's hOCR and TSV output.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BoundingBox {
+    pub left: i32,
+    pub top: i32,
+    pub right: i32,
+    pub bottom: i32,
+}
+
+impl BoundingBox {
+    pub fn new(left: i32, top: i32, right: i32, bottom: i32) -> Self {
+        BoundingBox {
+            left,
+            top,
+            right,
+            bottom,
+        }
+    }
+
+    pub fn width(&self) -> i32 {
+        self.right - self.left
+    }
+
+    pub fn height(&self) -> i32 {
+        self.bottom - self.top
+    }
+
+    pub fn area(&self) -> i64 {
+        if self.is_empty() {
+            return 0;
+        }
+        self.width() as i64 * self.height() as i64
+    }
+
+    /// Whether the box covers no pixels.
+    pub fn is_empty(&self) -> bool {
+        self.right <= self.left || self.bottom <= self.top
+    }
+
+    /// Overlapping part of both boxes, `None` if they do not overlap.
+    pub fn intersection(&self, other: &BoundingBox) -> Option<BoundingBox> {
+        let intersection = BoundingBox {
+            left: self.left.max(other.left),
+            top: self.top.max(other.top),
+            right: self.right.min(other.right),
+            bottom: self.bottom.min(other.bottom),
+        };
+        (!intersection.is_empty()).then_some(intersection)
+    }
+
+    /// Smallest box containing both boxes.
+    pub fn union(&self, other: &BoundingBox) -> BoundingBox {
+        BoundingBox {
+            left: self.left.min(other.left),
+            top: self.top.min(other.top),
+            right: self.right.max(other.right),
+            bottom: self.bottom.max(other.bottom),
+        }
+    }
+
+    /// Intersection over union from 0 (disjoint) to 1 (identical).
+    pub fn iou(&self, other: &BoundingBox) -> f64 {
+        let intersection = self.intersection(other).map_or(0, |x| x.area());
+        let union = self.area() + other.area() - intersection;
+        if union == 0 {
+            return 0.0;
+        }
+        intersection as f64 / union as f64
+    }
+
+    pub fn contains(&self, other: &BoundingBox) -> bool {
+        self.left <= other.left
+            && self.top <= other.top
+            && other.right <= self.right
+            && other.bottom <= self.bottom
+    }
+
+    pub fn contains_point(&self, point: Point) -> bool {
+        (self.left..self.right).contains(&point.x) && (self.top..self.bottom).contains(&point.y)
+    }
+
+    /// Euclidean distance between the closest edges, 0 if the boxes touch or
+    /// overlap.
+    pub fn distance(&self, other: &BoundingBox) -> f64 {
+        let dx = (other.left - self.right)
+            .max(self.left - other.right)
+            .max(0);
+        let dy = (other.top - self.bottom)
+            .max(self.top - other.bottom)
+            .max(0);
+        (dx as f64).hypot(dy as f64)
+    }
+
+    pub fn center(&self) -> (f64, f64) {
+        (
+            (self.left + self.right) as f64 / 2.0,
+            (self.top + self.bottom) as f64 / 2.0,
+        )
+    }
+
+    /// Box in an image resized by the given factors, rounded to whole pixels.
+    pub fn scale(&self, x: f64, y: f64) -> BoundingBox {
+        let scale = |value: i32, factor: f64| (value as f64 * factor).round() as i32;
+        BoundingBox {
+            left: scale(self.left, x),
+            top: scale(self.top, y),
+            right: scale(self.right, x),
+            bottom: scale(self.bottom, y),
+        }
+    }
+
+    pub fn translate(&self, dx: i32, dy: i32) -> BoundingBox {
+        BoundingBox::new(
+            self.left + dx,
+            self.top + dy,
+            self.right + dx,
+            self.bottom + dy,
+        )
+    }
+
+    /// Coordinates relative to an image of the given size, `None` if the
+    /// image is empty.
+    pub fn normalize(&self, image_width: u32, image_height: u32) -> Option<NormalizedBoundingBox> {
+        if image_width == 0 || image_height == 0 {
+            return None;
+        }
+        let (width, height) = (image_width as f64, image_height as f64);
+        Some(NormalizedBoundingBox {
+            left: self.left as f64 / width,
+            top: self.top as f64 / height,
+            right: self.right as f64 / width,
+            bottom: self.bottom as f64 / height,
+        })
+    }
+
+    /// Converts box file coordinates, which count y upwards from the bottom
+    /// edge of an image of `image_height` pixels.
+    pub fn from_bottom_left(
+        left: i32,
+        bottom: i32,
+        right: i32,
+        top: i32,
+        image_height: i32,
+    ) -> Self {
+        BoundingBox::new(left, image_height - top, right, image_height - bottom)
+    }
+
+    /// Inverse of [`BoundingBox::from_bottom_left`], returning
+    /// `(left, bottom, right, top)`.
+    pub fn to_bottom_left(&self, image_height: i32) -> (i32, i32, i32, i32) {
+        (
+            self.left,
+            image_height - self.bottom,
+            self.right,
+            image_height - self.top,
+        )
+    }
+
+    /// Smallest box spanned by the points of a polygon, `None` if it is empty.
+    pub fn enclosing(points: &[Point]) -> Option<Self> {
+        let first = points.first()?;
+        Some(points.iter().fold(
+            BoundingBox::new(first.x, first.y, first.x, first.y),
+            |bbox, point| BoundingBox {
+                left: bbox.left.min(point.x),
+                top: bbox.top.min(point.y),
+                right: bbox.right.max(point.x),
+                bottom: bbox.bottom.max(point.y),
+            },
+        ))
+    }
+}
+
+impl fmt::Display for BoundingBox {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} {} {} {}",
+            self.left, self.top, self.right, self.bottom
+        )
+    }
+}
+
+/// Bounding box with coordinates from 0 to 1 relative to the image size,
+/// independent of the resolution the image was recognized at.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct NormalizedBoundingBox {
+    pub left: f64,
+    pub top: f64,
+    pub right: f64,
+    pub bottom: f64,
+}
+
+impl NormalizedBoundingBox {
+    /// Pixel coordinates in an image of the given size, rounded to whole pixels.
+    pub fn denormalize(&self, image_width: u32, image_height: u32) -> BoundingBox {
+        let (width, height) = (image_width as f64, image_height as f64);
+        BoundingBox {
+            left: (self.left * width).round() as i32,
+            top: (self.top * height).round() as i32,
+            right: (self.right * width).round() as i32,
+            bottom: (self.bottom * height).round() as i32,
+        }
+    }
+}
+
+/// Pixel position with a top-left origin.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Point {
+    pub x: i32,
+    pub y: i32,
+}
+
+impl Point {
+    pub fn new(x: i32, y: i32) -> Self {
+        Point { x, y }
+    }
+}
+
+impl fmt::Display for Point {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{},{}", self.x, self.y)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    #[test]
+    fn test_bounding_box_operations() {
+        let a = BoundingBox::new(0, 0, 10, 10);
+        let b = BoundingBox::new(5, 5, 15, 15);
+        let c = BoundingBox::new(13, 14, 20, 20);
+
+        assert_eq!(a.intersection(&b), Some(BoundingBox::new(5, 5, 10, 10)));
+        assert_eq!(a.intersection(&c), None);
+        assert_eq!(a.union(&c), BoundingBox::new(0, 0, 20, 20));
+        assert_eq!(a.iou(&b), 25.0 / 175.0);
+        assert_eq!(a.iou(&a), 1.0);
+        assert!(a.union(&b).contains(&b));
+        assert!(!a.contains(&b));
+        assert!(a.contains_point(Point::new(9, 0)));
+        assert!(!a.contains_point(Point::new(10, 0)));
+        assert_eq!(a.distance(&b), 0.0);
+        assert_eq!(a.distance(&c), 5.0);
+        assert_eq!(a.scale(0.5, 2.0), BoundingBox::new(0, 0, 5, 20));
+        assert_eq!(a.translate(1, -1), BoundingBox::new(1, -1, 11, 9));
+    }
+
+    #[test]
+    fn test_bounding_box_conversions() {
+        // box file symbol 10 pixels above the bottom edge of a 100 pixel high image
+        let bbox = BoundingBox::from_bottom_left(5, 10, 15, 30, 100);
+        assert_eq!(bbox, BoundingBox::new(5, 70, 15, 90));
+        assert_eq!(bbox.to_bottom_left(100), (5, 10, 15, 30));
+
+        let normalized = bbox.normalize(20, 100).unwrap();
+        assert_eq!(bbox.normalize(0, 100), None);
+        assert_eq!(
+            normalized,
+            NormalizedBoundingBox {
+                left: 0.25,
+                top: 0.7,
+                right: 0.75,
+                bottom: 0.9
+            }
+        );
+        assert_eq!(normalized.denormalize(20, 100), bbox);
+        assert_eq!(
+            normalized.denormalize(40, 200),
+            BoundingBox::new(10, 140, 30, 180)
+        );
+    }
+}
@@ -1,45 +1,114 @@
 use image::DynamicImage;
 use std::{
-    collections::HashMap,
+    collections::BTreeMap,
     fmt::{self},
-    path::{Path, PathBuf},
+    fs,
+    io::{Cursor, Read},
+    path::PathBuf,
+    sync::{Arc, OnceLock},
+    time::Duration,
 };
 
-use crate::{TessError, TessResult};
+use crate::{
+    CancellationToken, ConfigParameterOutput, ConfigValue, InputFormat, OcrEngineMode, PageSegMode,
+    TessError, TessResult,
+};
 
 #[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
 pub struct Args {
     pub lang: String,
-    pub config_variables: HashMap<String, String>,
+    /// Passed as one `-c name=value` argument per variable, ordered by name.
+    pub config_variables: BTreeMap<String, ConfigValue>,
     pub dpi: i32,
-    pub psm: i32,
-    pub oem: i32,
+    pub psm: PageSegMode,
+    pub oem: OcrEngineMode,
+    /// Kill tesseract and fail with `TessError::TimeoutError` once exceeded.
+    #[cfg_attr(
+        feature = "serde",
+        serde(with = "crate::tesseract::serde_util::option_seconds")
+    )]
+    pub timeout: Option<Duration>,
+    /// Kill tesseract and fail with `TessError::CancelledError` once cancelled.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub cancellation: Option<CancellationToken>,
+    /// Detect the orientation with OSD first and recognize an upright copy of
+    /// the image. Applies to string, box and data output, whose coordinates
+    /// are mapped back to the original image; the raw `output` strings stay
+    /// as tesseract printed them for the upright copy.
+    pub auto_rotate: bool,
 }
 
 impl Default for Args {
     fn default() -> Self {
         Args {
             lang: "eng".into(),
-            config_variables: HashMap::new(),
+            config_variables: BTreeMap::new(),
             dpi: 150,
-            psm: 3,
-            oem: 3,
+            psm: PageSegMode::Auto,
+            oem: OcrEngineMode::Default,
+            timeout: None,
+            cancellation: None,
+            auto_rotate: false,
         }
     }
 }
 
 impl Args {
-    pub(crate) fn get_config_variable_args(&self) -> Option<String> {
-        if self.config_variables.is_empty() {
-            return None;
+    /// Rejects combinations tesseract cannot run, such as orientation and
+    /// script detection with the LSTM-only engine, and malformed config
+    /// variable names. Only the arguments themselves are checked, see
+    /// [`Tesseract::validate_args`] for checks against the installed
+    /// traineddata and parameters.
+    pub fn validate(&self) -> TessResult<()> {
+        if self.psm.uses_osd() && self.oem == OcrEngineMode::LstmOnly {
+            return Err(TessError::ArgumentError(format!(
+                "page segmentation mode {} needs the legacy engine for OSD, \
+                which OCR engine mode {} does not load",
+                self.psm, self.oem
+            )));
+        }
+        if let Some(name) = self
+            .config_variables
+            .keys()
+            .find(|x| x.is_empty() || !x.chars().all(|c| c.is_ascii_alphanumeric() || c == '_'))
+        {
+            return Err(TessError::ArgumentError(format!(
+                "invalid config variable name '{}'",
+                name
+            )));
         }
-        Some(
-            self.config_variables
+        Ok(())
+    }
+
+    /// Checks that every config variable is known to tesseract and that its
+    /// value fits the type inferred from the parameter's default.
+    pub fn validate_config_variables(&self, parameters: &ConfigParameterOutput) -> TessResult<()> {
+        for (name, value) in &self.config_variables {
+            let parameter = parameters
+                .config_parameters
                 .iter()
-                .map(|(key, value)| format!("{}={}", key, value))
-                .collect::<Vec<_>>()
-                .join(" "),
-        )
+                .find(|x| &x.name == name)
+                .ok_or_else(|| {
+                    TessError::ArgumentError(format!("unknown config variable '{}'", name))
+                })?;
+            let value_type = parameter.value_type();
+            if !value_type.accepts(value) {
+                return Err(TessError::ArgumentError(format!(
+                    "config variable '{}' expects a value of type {:?}, got '{}'",
+                    name, value_type, value
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    pub(crate) fn get_config_variable_args(&self) -> Vec<String> {
+        self.config_variables
+            .iter()
+            .map(|(key, value)| format!("{}={}", key, value))
+            .collect()
     }
 }
 
@@ -49,29 +118,53 @@ pub struct Image {
 }
 
 impl Image {
+    /// Uses the image file at `path`. The format is taken from the extension or,
+    /// if that is missing or unknown, from the file content. Formats tesseract
+    /// cannot read are converted and streamed through stdin.
     pub fn from_path<P: Into<PathBuf>>(path: P) -> TessResult<Self> {
         let path = path.into();
-        Self::check_image_format(&path)?;
+        let format = InputFormat::from_path(&path).ok_or(TessError::ImageFormatError)?;
+        if !format.is_supported_by_tesseract() {
+            let data = fs::read(&path).map_err(|e| TessError::ImageReadError(e.into()))?;
+            return Self::from_bytes_with_format(data, format);
+        }
         Ok(Self {
             data: InputData::Path(path),
         })
     }
 
-    fn check_image_format(path: &Path) -> TessResult<()> {
-        let binding = path
-            .extension()
-            .ok_or(TessError::ImageFormatError)?
-            .to_str()
-            .ok_or(TessError::ImageFormatError)?
-            .to_uppercase();
-        if matches!(
-            binding.as_str(),
-            "JPEG" | "JPG" | "PNG" | "PBM" | "PGM" | "PPM" | "TIFF" | "BMP" | "GIF" | "WEBP"
-        ) {
-            Ok(())
-        } else {
-            Err(TessError::ImageFormatError)
+    /// Uses an encoded image held in memory, detecting its format from the
+    /// magic bytes. The image is streamed to tesseract through stdin.
+    pub fn from_bytes<B: Into<Vec<u8>>>(data: B) -> TessResult<Self> {
+        let data = data.into();
+        let format = InputFormat::detect(&data).ok_or(TessError::ImageFormatError)?;
+        Self::from_bytes_with_format(data, format)
+    }
+
+    /// Reads an encoded image to the end, see [`Image::from_bytes`].
+    pub fn from_reader<R: Read>(mut reader: R) -> TessResult<Self> {
+        let mut data = Vec::new();
+        reader
+            .read_to_end(&mut data)
+            .map_err(|e| TessError::ImageReadError(e.into()))?;
+        Self::from_bytes(data)
+    }
+
+    fn from_bytes_with_format(data: Vec<u8>, format: InputFormat) -> TessResult<Self> {
+        if format.is_supported_by_tesseract() {
+            return Ok(Self {
+                data: InputData::Stdin {
+                    data: data.into(),
+                    extension: format.extension(),
+                    tempfile: OnceLock::new(),
+                },
+            });
         }
+
+        let image_format = format.image_format().ok_or(TessError::ImageFormatError)?;
+        let image = image::load_from_memory_with_format(&data, image_format)
+            .map_err(|e| TessError::DynamicImageError(e.to_string()))?;
+        Self::from_dynamic_image_via_stdin(&image, StdinFormat::default())
     }
 
     pub fn from_dynamic_image(image: &DynamicImage) -> TessResult<Self> {
@@ -91,31 +184,168 @@ impl Image {
         })
     }
 
+    /// Encodes the image in memory and streams it to tesseract through stdin
+    /// instead of storing it as a tempfile.
+    pub fn from_dynamic_image_via_stdin(
+        image: &DynamicImage,
+        format: StdinFormat,
+    ) -> TessResult<Self> {
+        let mut buffer = Cursor::new(Vec::new());
+        format
+            .prepare(image)
+            .write_to(&mut buffer, format.image_format())
+            .map_err(|e| TessError::DynamicImageError(e.to_string()))?;
+
+        Ok(Self {
+            data: InputData::Stdin {
+                data: buffer.into_inner().into(),
+                extension: format.extension(),
+                tempfile: OnceLock::new(),
+            },
+        })
+    }
+
+    /// Decodes the image, e.g. to transform it before recognition.
+    pub(crate) fn to_dynamic_image(&self) -> TessResult<DynamicImage> {
+        match &self.data {
+            InputData::Stdin { data, .. } => image::load_from_memory(data),
+            _ => image::open(self.get_image_path()?),
+        }
+        .map_err(|e| TessError::DynamicImageError(e.to_string()))
+    }
+
+    /// Path of the image on disk. Images streamed through stdin are written to
+    /// a tempfile on first use.
     pub(crate) fn get_image_path(&self) -> TessResult<&str> {
         match &self.data {
             InputData::Path(x) => x.to_str(),
             InputData::Image(x) => x.path().to_str(),
+            InputData::Stdin {
+                data,
+                extension,
+                tempfile,
+            } => {
+                if tempfile.get().is_none() {
+                    let file = write_tempfile(data, extension)?;
+                    let _ = tempfile.set(file);
+                }
+                tempfile.get().and_then(|x| x.path().to_str())
+            }
         }
         .ok_or(TessError::ImageNotFoundError)
     }
+
+    /// Input argument passed to tesseract, either the image path or `stdin`.
+    pub(crate) fn get_input_arg(&self) -> TessResult<&str> {
+        match &self.data {
+            InputData::Stdin { .. } => Ok("stdin"),
+            _ => self.get_image_path(),
+        }
+    }
+
+    pub(crate) fn get_stdin_data(&self) -> Option<Arc<[u8]>> {
+        match &self.data {
+            InputData::Stdin { data, .. } => Some(data.clone()),
+            _ => None,
+        }
+    }
+}
+
+fn write_tempfile(data: &[u8], extension: &str) -> TessResult<tempfile::NamedTempFile> {
+    let mut tempfile = tempfile::Builder::new()
+        .prefix("rusty-tesseract")
+        .suffix(&format!(".{}", extension))
+        .tempfile()
+        .map_err(|e| TessError::TempfileError(e.to_string()))?;
+    std::io::Write::write_all(&mut tempfile, data)
+        .map_err(|e| TessError::TempfileError(e.to_string()))?;
+    Ok(tempfile)
+}
+
+/// Encoding used for images streamed to tesseract through stdin.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum StdinFormat {
+    /// Uncompressed PBM/PGM/PPM, the cheapest format to encode.
+    #[default]
+    Pnm,
+    Png,
+    /// Uncompressed TIFF.
+    Tiff,
+}
+
+impl StdinFormat {
+    fn image_format(self) -> image::ImageFormat {
+        match self {
+            StdinFormat::Pnm => image::ImageFormat::Pnm,
+            StdinFormat::Png => image::ImageFormat::Png,
+            StdinFormat::Tiff => image::ImageFormat::Tiff,
+        }
+    }
+
+    fn extension(self) -> &'static str {
+        match self {
+            StdinFormat::Pnm => "pnm",
+            StdinFormat::Png => "png",
+            StdinFormat::Tiff => "tif",
+        }
+    }
+
+    /// Converts color types the encoder cannot write to the closest 8-bit type.
+    fn prepare(self, image: &DynamicImage) -> DynamicImage {
+        match (self, image) {
+            (StdinFormat::Png, _)
+            | (_, DynamicImage::ImageLuma8(_))
+            | (_, DynamicImage::ImageRgb8(_)) => image.clone(),
+            (StdinFormat::Tiff, DynamicImage::ImageRgba8(_)) => image.clone(),
+            (_, DynamicImage::ImageLumaA8(_))
+            | (_, DynamicImage::ImageLuma16(_))
+            | (_, DynamicImage::ImageLumaA16(_)) => DynamicImage::ImageLuma8(image.to_luma8()),
+            _ => DynamicImage::ImageRgb8(image.to_rgb8()),
+        }
+    }
 }
 
-#[derive(Debug)]
 enum InputData {
     Path(PathBuf),
     Image(tempfile::NamedTempFile),
+    Stdin {
+        data: Arc<[u8]>,
+        extension: &'static str,
+        tempfile: OnceLock<tempfile::NamedTempFile>,
+    },
+}
+
+impl fmt::Debug for InputData {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InputData::Path(x) => f.debug_tuple("Path").field(x).finish(),
+            InputData::Image(x) => f.debug_tuple("Image").field(x).finish(),
+            InputData::Stdin {
+                data, extension, ..
+            } => f
+                .debug_struct("Stdin")
+                .field("len", &data.len())
+                .field("extension", extension)
+                .finish(),
+        }
+    }
 }
 
 impl fmt::Display for Image {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{}", self.get_image_path().unwrap())
+        write!(f, "{}", self.get_input_arg().unwrap())
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::Image;
+    use super::{Image, StdinFormat};
+    use crate::{
+        Args, ConfigParameterOutput, ConfigValue, OcrEngineMode, PageSegMode, ProcessOutput,
+        TessError,
+    };
     use image::io::Reader as ImageReader;
+    use std::collections::BTreeMap;
 
     #[test]
     fn test_from_path() {
@@ -139,4 +369,159 @@ mod tests {
 
         assert_eq!(img, tempimg);
     }
+
+    #[test]
+    fn test_from_dynamic_image_via_stdin() {
+        let img = ImageReader::open("img/string.png")
+            .unwrap()
+            .decode()
+            .unwrap();
+
+        let input = Image::from_dynamic_image_via_stdin(&img, StdinFormat::Pnm).unwrap();
+        assert_eq!(input.get_input_arg().unwrap(), "stdin");
+        assert!(input.get_stdin_data().unwrap().starts_with(b"P"));
+
+        let input = Image::from_dynamic_image_via_stdin(&img, StdinFormat::Png).unwrap();
+        let temppath = input.get_image_path().unwrap();
+        assert!(temppath.ends_with(".png"));
+
+        let tempimg = ImageReader::open(temppath).unwrap().decode().unwrap();
+        assert_eq!(img, tempimg);
+    }
+
+    #[test]
+    fn test_from_bytes() {
+        let data = std::fs::read("img/string.png").unwrap();
+        let input = Image::from_bytes(data.clone()).unwrap();
+        assert_eq!(input.get_input_arg().unwrap(), "stdin");
+        assert_eq!(&*input.get_stdin_data().unwrap(), data.as_slice());
+
+        let input = Image::from_reader(data.as_slice()).unwrap();
+        assert!(input.get_image_path().unwrap().ends_with(".png"));
+
+        assert_eq!(
+            Image::from_bytes(b"not an image".to_vec()).unwrap_err(),
+            TessError::ImageFormatError
+        );
+    }
+
+    #[test]
+    fn test_from_bytes_converts_unsupported_format() {
+        let img = ImageReader::open("img/string.png")
+            .unwrap()
+            .decode()
+            .unwrap();
+        let mut qoi = std::io::Cursor::new(Vec::new());
+        img.write_to(&mut qoi, image::ImageFormat::Qoi).unwrap();
+
+        let input = Image::from_bytes(qoi.into_inner()).unwrap();
+        assert!(input.get_stdin_data().unwrap().starts_with(b"P"));
+    }
+
+    #[test]
+    fn test_from_path_without_extension() {
+        let dir = tempfile::tempdir().unwrap();
+        let upload = dir.path().join("upload");
+        std::fs::copy("img/string.png", &upload).unwrap();
+
+        let input = Image::from_path(&upload).unwrap();
+        assert_eq!(input.get_image_path().unwrap(), upload.to_str().unwrap());
+        assert_eq!(
+            Image::from_path("img/missing").unwrap_err(),
+            TessError::ImageFormatError
+        );
+    }
+
+    #[test]
+    fn test_validate_args() {
+        assert_eq!(Args::default().validate(), Ok(()));
+
+        let args = Args {
+            psm: PageSegMode::OsdOnly,
+            oem: OcrEngineMode::LstmOnly,
+            ..Default::default()
+        };
+        assert!(matches!(args.validate(), Err(TessError::ArgumentError(_))));
+    }
+
+    #[test]
+    fn test_get_config_variable_args() {
+        let args = Args {
+            config_variables: BTreeMap::from([
+                ("tessedit_char_whitelist".into(), "ABC".into()),
+                ("preserve_interword_spaces".into(), true.into()),
+                ("textord_min_xheight".into(), 10.into()),
+            ]),
+            ..Default::default()
+        };
+        assert_eq!(
+            args.get_config_variable_args(),
+            vec![
+                "preserve_interword_spaces=1",
+                "tessedit_char_whitelist=ABC",
+                "textord_min_xheight=10"
+            ]
+        );
+
+        let args = Args {
+            config_variables: BTreeMap::from([("a b".into(), ConfigValue::Int(1))]),
+            ..Default::default()
+        };
+        assert!(matches!(args.validate(), Err(TessError::ArgumentError(_))));
+    }
+
+    #[test]
+    fn test_validate_config_variables() {
+        let parameters = ConfigParameterOutput::from_output(ProcessOutput {
+            stdout: "Tesseract parameters:\n\
+                textord_min_xheight\t10\tMin credible pixel xheight for AI\n\
+                tessedit_create_hocr\t0\tWrite .html hOCR output file\n\
+                tessedit_char_whitelist\t\tWhitelist of chars to recognize\n"
+                .into(),
+            diagnostics: vec![],
+        })
+        .unwrap();
+        let invalid = |name: &str, value_type: &str, value: &str| {
+            Err(TessError::ArgumentError(format!(
+                "config variable '{}' expects a value of type {}, got '{}'",
+                name, value_type, value
+            )))
+        };
+
+        let mut args = Args {
+            config_variables: BTreeMap::from([
+                ("textord_min_xheight".into(), "12".into()),
+                ("tessedit_char_whitelist".into(), "ABC".into()),
+            ]),
+            ..Default::default()
+        };
+        assert_eq!(args.validate_config_variables(&parameters), Ok(()));
+
+        args.config_variables
+            .insert("tessedit_create_hocr".into(), true.into());
+        assert_eq!(args.validate_config_variables(&parameters), Ok(()));
+
+        for (name, value, value_type) in [
+            ("textord_min_xheight", ConfigValue::from("high"), "Int"),
+            ("textord_min_xheight", ConfigValue::from(2.5), "Int"),
+            ("tessedit_create_hocr", ConfigValue::from(0.5), "Bool"),
+            ("tessedit_create_hocr", ConfigValue::from(7), "Bool"),
+        ] {
+            let mut args = args.clone();
+            args.config_variables.insert(name.into(), value.clone());
+            assert_eq!(
+                args.validate_config_variables(&parameters),
+                invalid(name, value_type, &value.to_string())
+            );
+        }
+
+        args.config_variables.clear();
+        args.config_variables.insert("unknown".into(), true.into());
+        assert_eq!(
+            args.validate_config_variables(&parameters),
+            Err(TessError::ArgumentError(
+                "unknown config variable 'unknown'".into()
+            ))
+        );
+    }
 }
@@ -0,0 +1,181 @@
+use std::{fs::File, io::Read, path::Path};
+
+/// Image container formats recognized by content or file extension.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum InputFormat {
+    Jpeg,
+    Png,
+    Gif,
+    Bmp,
+    Tiff,
+    /// PBM, PGM, PPM and PAM.
+    Pnm,
+    Webp,
+    /// JPEG 2000, either as JP2 container or raw codestream.
+    Jp2,
+    /// Leptonica's serialized pix format.
+    Spix,
+    #[cfg(feature = "avif")]
+    Avif,
+    Qoi,
+    Ico,
+    Hdr,
+}
+
+impl InputFormat {
+    /// Detects the format from the magic bytes at the start of `bytes`.
+    pub fn detect(bytes: &[u8]) -> Option<Self> {
+        let format = match bytes {
+            [0xFF, 0xD8, 0xFF, ..] => InputFormat::Jpeg,
+            [0x89, b'P', b'N', b'G', 0x0D, 0x0A, 0x1A, 0x0A, ..] => InputFormat::Png,
+            [b'G', b'I', b'F', b'8', b'7' | b'9', b'a', ..] => InputFormat::Gif,
+            [b'B', b'M', ..] => InputFormat::Bmp,
+            [b'I', b'I', 0x2A, 0x00, ..] | [b'M', b'M', 0x00, 0x2A, ..] => InputFormat::Tiff,
+            [b'P', b'1'..=b'7', b' ' | b'\t' | b'\r' | b'\n', ..] => InputFormat::Pnm,
+            [b'R', b'I', b'F', b'F', _, _, _, _, b'W', b'E', b'B', b'P', ..] => InputFormat::Webp,
+            [0x00, 0x00, 0x00, 0x0C, b'j', b'P', b' ', b' ', ..] | [0xFF, 0x4F, 0xFF, 0x51, ..] => {
+                InputFormat::Jp2
+            }
+            [b's', b'p', b'i', b'x', ..] => InputFormat::Spix,
+            #[cfg(feature = "avif")]
+            [_, _, _, _, b'f', b't', b'y', b'p', b'a', b'v', b'i', b'f' | b's', ..] => {
+                InputFormat::Avif
+            }
+            [b'q', b'o', b'i', b'f', ..] => InputFormat::Qoi,
+            [0x00, 0x00, 0x01, 0x00, ..] => InputFormat::Ico,
+            [b'#', b'?', b'R', b'A', b'D', b'I', b'A', b'N', b'C', b'E', ..]
+            | [b'#', b'?', b'R', b'G', b'B', b'E', ..] => InputFormat::Hdr,
+            _ => return None,
+        };
+        Some(format)
+    }
+
+    /// Detects the format from a case-insensitive file extension.
+    pub fn from_extension(extension: &str) -> Option<Self> {
+        let format = match extension.to_uppercase().as_str() {
+            "JPEG" | "JPG" => InputFormat::Jpeg,
+            "PNG" => InputFormat::Png,
+            "GIF" => InputFormat::Gif,
+            "BMP" => InputFormat::Bmp,
+            "TIFF" | "TIF" => InputFormat::Tiff,
+            "PBM" | "PGM" | "PPM" | "PNM" | "PAM" => InputFormat::Pnm,
+            "WEBP" => InputFormat::Webp,
+            "JP2" | "J2K" => InputFormat::Jp2,
+            "SPIX" => InputFormat::Spix,
+            #[cfg(feature = "avif")]
+            "AVIF" => InputFormat::Avif,
+            "QOI" => InputFormat::Qoi,
+            "ICO" => InputFormat::Ico,
+            "HDR" => InputFormat::Hdr,
+            _ => return None,
+        };
+        Some(format)
+    }
+
+    /// Detects the format from the file extension, falling back to the file
+    /// content for unknown or missing extensions.
+    pub fn from_path(path: &Path) -> Option<Self> {
+        path.extension()
+            .and_then(|x| x.to_str())
+            .and_then(Self::from_extension)
+            .or_else(|| {
+                let mut header = Vec::with_capacity(16);
+                File::open(path)
+                    .and_then(|file| file.take(16).read_to_end(&mut header))
+                    .ok()?;
+                Self::detect(&header)
+            })
+    }
+
+    /// Whether Leptonica reads the format directly. Other formats are decoded
+    /// with the `image` crate before they are passed to tesseract.
+    pub fn is_supported_by_tesseract(self) -> bool {
+        match self {
+            #[cfg(feature = "avif")]
+            InputFormat::Avif => false,
+            InputFormat::Qoi | InputFormat::Ico | InputFormat::Hdr => false,
+            _ => true,
+        }
+    }
+
+    pub(crate) fn extension(self) -> &'static str {
+        match self {
+            InputFormat::Jpeg => "jpg",
+            InputFormat::Png => "png",
+            InputFormat::Gif => "gif",
+            InputFormat::Bmp => "bmp",
+            InputFormat::Tiff => "tif",
+            InputFormat::Pnm => "pnm",
+            InputFormat::Webp => "webp",
+            InputFormat::Jp2 => "jp2",
+            InputFormat::Spix => "spix",
+            #[cfg(feature = "avif")]
+            InputFormat::Avif => "avif",
+            InputFormat::Qoi => "qoi",
+            InputFormat::Ico => "ico",
+            InputFormat::Hdr => "hdr",
+        }
+    }
+
+    pub(crate) fn image_format(self) -> Option<image::ImageFormat> {
+        let format = match self {
+            InputFormat::Jpeg => image::ImageFormat::Jpeg,
+            InputFormat::Png => image::ImageFormat::Png,
+            InputFormat::Gif => image::ImageFormat::Gif,
+            InputFormat::Bmp => image::ImageFormat::Bmp,
+            InputFormat::Tiff => image::ImageFormat::Tiff,
+            InputFormat::Pnm => image::ImageFormat::Pnm,
+            InputFormat::Webp => image::ImageFormat::WebP,
+            #[cfg(feature = "avif")]
+            InputFormat::Avif => image::ImageFormat::Avif,
+            InputFormat::Qoi => image::ImageFormat::Qoi,
+            InputFormat::Ico => image::ImageFormat::Ico,
+            InputFormat::Hdr => image::ImageFormat::Hdr,
+            InputFormat::Jp2 | InputFormat::Spix => return None,
+        };
+        Some(format)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::InputFormat;
+
+    #[test]
+    fn test_detect() {
+        let cases: [(&[u8], Option<InputFormat>); 8] = [
+            (b"\xFF\xD8\xFF\xE0\x00\x10JFIF", Some(InputFormat::Jpeg)),
+            (b"II*\x00\x08\x00\x00\x00", Some(InputFormat::Tiff)),
+            (b"P4\n10 10\n", Some(InputFormat::Pnm)),
+            (b"RIFF\x24\x00\x00\x00WEBPVP8 ", Some(InputFormat::Webp)),
+            #[cfg(feature = "avif")]
+            (b"\x00\x00\x00\x1CftypavifAAAA", Some(InputFormat::Avif)),
+            #[cfg(not(feature = "avif"))]
+            (b"\x00\x00\x00\x1CftypavifAAAA", None),
+            (b"qoif\x00\x00\x01\x00", Some(InputFormat::Qoi)),
+            (b"#?RADIANCE\n", Some(InputFormat::Hdr)),
+            (b"%PDF-1.7", None),
+        ];
+        for (bytes, expected) in cases {
+            assert_eq!(InputFormat::detect(bytes), expected);
+        }
+        assert_eq!(
+            InputFormat::detect(&std::fs::read("img/string.png").unwrap()),
+            Some(InputFormat::Png)
+        );
+    }
+
+    #[test]
+    fn test_from_path() {
+        assert_eq!(
+            InputFormat::from_path("scan.TIF".as_ref()),
+            Some(InputFormat::Tiff)
+        );
+
+        let dir = tempfile::tempdir().unwrap();
+        let upload = dir.path().join("upload");
+        std::fs::copy("img/string.png", &upload).unwrap();
+        assert_eq!(InputFormat::from_path(&upload), Some(InputFormat::Png));
+        assert_eq!(InputFormat::from_path("missing".as_ref()), None);
+    }
+}
@@ -0,0 +1,284 @@
+use crate::{TessError, TessResult};
+use core::fmt;
+use std::{
+    hash::{Hash, Hasher},
+    mem::discriminant,
+    str::FromStr,
+};
+
+/// Page segmentation mode passed as `--psm`.
+///
+/// Modes compare by their numeric value, so `Raw(6)` equals `SingleBlock`.
+#[derive(Clone, Copy, Debug, Default, Eq)]
+pub enum PageSegMode {
+    OsdOnly,
+    AutoOsd,
+    AutoOnly,
+    #[default]
+    Auto,
+    SingleColumn,
+    SingleBlockVertText,
+    SingleBlock,
+    SingleLine,
+    SingleWord,
+    CircleWord,
+    SingleChar,
+    SparseText,
+    SparseTextOsd,
+    RawLine,
+    /// Mode unknown to this crate, passed to tesseract unchanged. Only built
+    /// explicitly, parsing rejects numbers of unknown modes.
+    Raw(i32),
+}
+
+impl PageSegMode {
+    pub const ALL: [PageSegMode; 14] = [
+        PageSegMode::OsdOnly,
+        PageSegMode::AutoOsd,
+        PageSegMode::AutoOnly,
+        PageSegMode::Auto,
+        PageSegMode::SingleColumn,
+        PageSegMode::SingleBlockVertText,
+        PageSegMode::SingleBlock,
+        PageSegMode::SingleLine,
+        PageSegMode::SingleWord,
+        PageSegMode::CircleWord,
+        PageSegMode::SingleChar,
+        PageSegMode::SparseText,
+        PageSegMode::SparseTextOsd,
+        PageSegMode::RawLine,
+    ];
+
+    pub fn value(self) -> i32 {
+        match self {
+            PageSegMode::Raw(value) => value,
+            mode => position(&Self::ALL, mode),
+        }
+    }
+
+    /// The named mode for a `Raw` value this crate knows.
+    fn named(self) -> Self {
+        Self::try_from(self.value()).unwrap_or(self)
+    }
+
+    pub fn description(self) -> &'static str {
+        match self.named() {
+            PageSegMode::OsdOnly => "Orientation and script detection (OSD) only.",
+            PageSegMode::AutoOsd => "Automatic page segmentation with OSD.",
+            PageSegMode::AutoOnly => {
+                "Automatic page segmentation, but no OSD, or OCR. (not implemented)"
+            }
+            PageSegMode::Auto => "Fully automatic page segmentation, but no OSD. (Default)",
+            PageSegMode::SingleColumn => "Assume a single column of text of variable sizes.",
+            PageSegMode::SingleBlockVertText => {
+                "Assume a single uniform block of vertically aligned text."
+            }
+            PageSegMode::SingleBlock => "Assume a single uniform block of text.",
+            PageSegMode::SingleLine => "Treat the image as a single text line.",
+            PageSegMode::SingleWord => "Treat the image as a single word.",
+            PageSegMode::CircleWord => "Treat the image as a single word in a circle.",
+            PageSegMode::SingleChar => "Treat the image as a single character.",
+            PageSegMode::SparseText => "Sparse text. Find as much text as possible in no particular order.",
+            PageSegMode::SparseTextOsd => "Sparse text with OSD.",
+            PageSegMode::RawLine => {
+                "Raw line. Treat the image as a single text line, bypassing hacks that are Tesseract-specific."
+            }
+            PageSegMode::Raw(_) => "Unknown page segmentation mode.",
+        }
+    }
+
+    /// Whether the mode runs orientation and script detection, which needs the
+    /// legacy engine.
+    pub fn uses_osd(self) -> bool {
+        matches!(
+            self.named(),
+            PageSegMode::OsdOnly | PageSegMode::AutoOsd | PageSegMode::SparseTextOsd
+        )
+    }
+}
+
+impl TryFrom<i32> for PageSegMode {
+    type Error = TessError;
+
+    fn try_from(value: i32) -> Result<Self, Self::Error> {
+        from_value(&Self::ALL, value, "page segmentation mode")
+    }
+}
+
+impl PartialEq for PageSegMode {
+    fn eq(&self, other: &Self) -> bool {
+        self.value() == other.value()
+    }
+}
+
+impl Hash for PageSegMode {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.value().hash(state)
+    }
+}
+
+impl fmt::Display for PageSegMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.value())
+    }
+}
+
+impl FromStr for PageSegMode {
+    type Err = TessError;
+
+    /// Accepts the numeric value or the variant name, e.g. `6`, `SingleBlock`
+    /// or `single_block`.
+    fn from_str(s: &str) -> TessResult<Self> {
+        parse_mode(s, &Self::ALL, "page segmentation mode")
+    }
+}
+
+/// OCR engine mode passed as `--oem`.
+///
+/// Modes compare by their numeric value, so `Raw(1)` equals `LstmOnly`.
+#[derive(Clone, Copy, Debug, Default, Eq)]
+pub enum OcrEngineMode {
+    TesseractOnly,
+    LstmOnly,
+    TesseractLstmCombined,
+    #[default]
+    Default,
+    /// Mode unknown to this crate, passed to tesseract unchanged. Only built
+    /// explicitly, parsing rejects numbers of unknown modes.
+    Raw(i32),
+}
+
+impl OcrEngineMode {
+    pub const ALL: [OcrEngineMode; 4] = [
+        OcrEngineMode::TesseractOnly,
+        OcrEngineMode::LstmOnly,
+        OcrEngineMode::TesseractLstmCombined,
+        OcrEngineMode::Default,
+    ];
+
+    pub fn value(self) -> i32 {
+        match self {
+            OcrEngineMode::Raw(value) => value,
+            mode => position(&Self::ALL, mode),
+        }
+    }
+
+    pub fn description(self) -> &'static str {
+        match Self::try_from(self.value()).unwrap_or(self) {
+            OcrEngineMode::TesseractOnly => "Legacy engine only.",
+            OcrEngineMode::LstmOnly => "Neural nets LSTM engine only.",
+            OcrEngineMode::TesseractLstmCombined => "Legacy + LSTM engines.",
+            OcrEngineMode::Default => "Default, based on what is available.",
+            OcrEngineMode::Raw(_) => "Unknown OCR engine mode.",
+        }
+    }
+}
+
+impl TryFrom<i32> for OcrEngineMode {
+    type Error = TessError;
+
+    fn try_from(value: i32) -> Result<Self, Self::Error> {
+        from_value(&Self::ALL, value, "OCR engine mode")
+    }
+}
+
+impl PartialEq for OcrEngineMode {
+    fn eq(&self, other: &Self) -> bool {
+        self.value() == other.value()
+    }
+}
+
+impl Hash for OcrEngineMode {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.value().hash(state)
+    }
+}
+
+impl fmt::Display for OcrEngineMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.value())
+    }
+}
+
+impl FromStr for OcrEngineMode {
+    type Err = TessError;
+
+    /// Accepts the numeric value or the variant name, e.g. `1`, `LstmOnly`
+    /// or `lstm_only`.
+    fn from_str(s: &str) -> TessResult<Self> {
+        parse_mode(s, &Self::ALL, "OCR engine mode")
+    }
+}
+
+/// Index of the named mode in `all`, compared by variant since equality
+/// goes through the value.
+fn position<T>(all: &[T], mode: T) -> i32 {
+    all.iter()
+        .position(|x| discriminant(x) == discriminant(&mode))
+        .unwrap() as i32
+}
+
+fn from_value<T: Copy>(all: &[T], value: i32, kind: &str) -> TessResult<T> {
+    usize::try_from(value)
+        .ok()
+        .and_then(|index| all.get(index).copied())
+        .ok_or_else(|| TessError::ArgumentError(format!("unknown {} '{}'", kind, value)))
+}
+
+fn parse_mode<T>(s: &str, all: &[T], kind: &str) -> TessResult<T>
+where
+    T: Copy + fmt::Debug + TryFrom<i32, Error = TessError>,
+{
+    let s = s.trim();
+    if let Ok(value) = s.parse::<i32>() {
+        return T::try_from(value);
+    }
+    let normalize = |x: &str| x.replace(['_', '-'], "").to_lowercase();
+    all.iter()
+        .find(|mode| normalize(&format!("{:?}", mode)) == normalize(s))
+        .copied()
+        .ok_or_else(|| TessError::ArgumentError(format!("unknown {} '{}'", kind, s)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_page_seg_mode_values() {
+        for (index, mode) in PageSegMode::ALL.iter().enumerate() {
+            assert_eq!(mode.value(), index as i32);
+            assert_eq!(PageSegMode::try_from(index as i32), Ok(*mode));
+        }
+        assert_eq!(
+            PageSegMode::try_from(14),
+            Err(TessError::ArgumentError(
+                "unknown page segmentation mode '14'".into()
+            ))
+        );
+        assert!(OcrEngineMode::try_from(-1).is_err());
+        assert_eq!(PageSegMode::SingleBlock.to_string(), "6");
+
+        // explicitly built raw modes are passed through and equal their names
+        assert_eq!(PageSegMode::Raw(14).value(), 14);
+        assert_eq!(PageSegMode::Raw(6), PageSegMode::SingleBlock);
+        assert_eq!(OcrEngineMode::Raw(1), OcrEngineMode::LstmOnly);
+        assert!(PageSegMode::Raw(0).uses_osd());
+    }
+
+    #[test]
+    fn test_from_str() {
+        assert_eq!("6".parse(), Ok(PageSegMode::SingleBlock));
+        assert_eq!("single_block".parse(), Ok(PageSegMode::SingleBlock));
+        assert_eq!("SparseTextOsd".parse(), Ok(PageSegMode::SparseTextOsd));
+        assert_eq!("lstm-only".parse(), Ok(OcrEngineMode::LstmOnly));
+        assert!("14".parse::<PageSegMode>().is_err());
+        assert!("4".parse::<OcrEngineMode>().is_err());
+        assert_eq!(
+            "blocky".parse::<PageSegMode>(),
+            Err(TessError::ArgumentError(
+                "unknown page segmentation mode 'blocky'".into()
+            ))
+        );
+    }
+}
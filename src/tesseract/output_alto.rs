@@ -0,0 +1,402 @@
+use super::*;
+use core::fmt;
+use roxmltree::Node;
+
+/// Namespaces of the ALTO versions tesseract emits.
+pub const ALTO_NAMESPACES: [&str; 2] = [
+    "http://www.loc.gov/standards/alto/ns-v3#",
+    "http://www.loc.gov/standards/alto/ns-v4#",
+];
+
+#[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AltoOutput {
+    pub output: String,
+    /// Namespace of the root element, one of [`ALTO_NAMESPACES`].
+    pub namespace: String,
+    pub measurement_unit: String,
+    pub pages: Vec<AltoPage>,
+    pub diagnostics: Vec<Diagnostic>,
+}
+
+impl AltoOutput {
+    pub(crate) fn from_output(output: ProcessOutput) -> TessResult<Self> {
+        let (namespace, measurement_unit, pages) = parse_alto(&output.stdout)?;
+        Ok(AltoOutput {
+            output: output.stdout,
+            namespace,
+            measurement_unit,
+            pages,
+            diagnostics: output.diagnostics,
+        })
+    }
+
+    pub fn strings(&self) -> impl Iterator<Item = &AltoString> {
+        self.pages
+            .iter()
+            .flat_map(|x| &x.print_space.text_blocks)
+            .flat_map(|x| &x.text_lines)
+            .flat_map(|x| &x.elements)
+            .filter_map(|x| match x {
+                AltoLineElement::String(x) => Some(x),
+                _ => None,
+            })
+    }
+}
+
+impl fmt::Display for AltoOutput {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.output)
+    }
+}
+
+#[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AltoPage {
+    pub id: String,
+    pub width: i32,
+    pub height: i32,
+    pub physical_img_nr: i32,
+    pub print_space: AltoPrintSpace,
+}
+
+#[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AltoPrintSpace {
+    pub bbox: BoundingBox,
+    /// Text blocks in document order, including those nested in composed blocks.
+    pub text_blocks: Vec<AltoTextBlock>,
+}
+
+#[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AltoTextBlock {
+    pub id: String,
+    pub bbox: BoundingBox,
+    pub text_lines: Vec<AltoTextLine>,
+}
+
+#[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AltoTextLine {
+    pub id: String,
+    pub bbox: BoundingBox,
+    pub elements: Vec<AltoLineElement>,
+}
+
+/// Child of a `TextLine` in document order.
+#[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum AltoLineElement {
+    String(AltoString),
+    /// `SP`, white space between two strings.
+    Space(AltoSpace),
+    /// `HYP`, hyphenation mark at the end of the line.
+    Hyphen(AltoHyphen),
+}
+
+#[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AltoString {
+    pub id: String,
+    pub bbox: BoundingBox,
+    pub content: String,
+    /// `WC`, word confidence between 0 and 1.
+    pub confidence: Option<f32>,
+}
+
+#[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AltoSpace {
+    pub hpos: i32,
+    pub vpos: i32,
+    pub width: i32,
+}
+
+#[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AltoHyphen {
+    pub hpos: i32,
+    pub vpos: i32,
+    pub width: i32,
+    pub content: String,
+}
+
+impl Tesseract {
+    pub fn image_to_alto(&self, image: &Image, args: &Args) -> TessResult<AltoOutput> {
+        let mut command = self.create_tesseract_command(image, args)?;
+        command.arg("alto");
+
+        let output = run_tesseract_command(&mut command, RunOptions::new(image, args))?;
+        AltoOutput::from_output(output)
+    }
+}
+
+pub fn image_to_alto(image: &Image, args: &Args) -> TessResult<AltoOutput> {
+    Tesseract::shared().image_to_alto(image, args)
+}
+
+fn parse_alto(output: &str) -> TessResult<(String, String, Vec<AltoPage>)> {
+    let document = roxmltree::Document::parse(output)
+        .map_err(|e| TessError::ParseError(format!("ALTO output: {}", e)))?;
+
+    let root = document.root_element();
+    let namespace = root.tag_name().namespace().unwrap_or_default();
+    if root.tag_name().name() != "alto" || !ALTO_NAMESPACES.contains(&namespace) {
+        return Err(schema_error(format!(
+            "root element must be 'alto' in one of {:?}",
+            ALTO_NAMESPACES
+        )));
+    }
+
+    let measurement_unit = child(root, "Description")
+        .and_then(|x| child(x, "MeasurementUnit"))
+        .and_then(|x| x.text())
+        .ok_or_else(|| schema_error("missing Description/MeasurementUnit".into()))?;
+    if !matches!(measurement_unit, "pixel" | "mm10" | "inch1200") {
+        return Err(schema_error(format!(
+            "invalid MeasurementUnit '{}'",
+            measurement_unit
+        )));
+    }
+
+    let layout = child(root, "Layout").ok_or_else(|| schema_error("missing Layout".into()))?;
+    let pages = children(layout, "Page")
+        .map(|page| {
+            let print_space = child(page, "PrintSpace").ok_or_else(|| {
+                schema_error(format!("missing PrintSpace in Page '{}'", id(page)))
+            })?;
+            Ok(AltoPage {
+                id: required(page, "ID")?.into(),
+                width: number(page, "WIDTH")?,
+                height: number(page, "HEIGHT")?,
+                physical_img_nr: number(page, "PHYSICAL_IMG_NR")?,
+                print_space: AltoPrintSpace {
+                    bbox: bbox(print_space)?,
+                    text_blocks: print_space
+                        .descendants()
+                        .filter(|x| x.has_tag_name((namespace, "TextBlock")))
+                        .map(parse_text_block)
+                        .collect::<TessResult<_>>()?,
+                },
+            })
+        })
+        .collect::<TessResult<_>>()?;
+
+    Ok((namespace.into(), measurement_unit.into(), pages))
+}
+
+fn parse_text_block(block: Node) -> TessResult<AltoTextBlock> {
+    Ok(AltoTextBlock {
+        id: required(block, "ID")?.into(),
+        bbox: bbox(block)?,
+        text_lines: children(block, "TextLine")
+            .map(|line| {
+                Ok(AltoTextLine {
+                    id: required(line, "ID")?.into(),
+                    bbox: bbox(line)?,
+                    elements: line
+                        .children()
+                        .filter(|x| x.is_element())
+                        .map(parse_line_element)
+                        .collect::<TessResult<_>>()?,
+                })
+            })
+            .collect::<TessResult<_>>()?,
+    })
+}
+
+fn parse_line_element(element: Node) -> TessResult<AltoLineElement> {
+    match element.tag_name().name() {
+        "String" => {
+            let confidence = element
+                .attribute("WC")
+                .map(|x| {
+                    x.parse::<f32>()
+                        .ok()
+                        .filter(|x| (0.0..=1.0).contains(x))
+                        .ok_or_else(|| schema_error(format!("invalid WC '{}'", x)))
+                })
+                .transpose()?;
+            Ok(AltoLineElement::String(AltoString {
+                id: id(element),
+                bbox: bbox(element)?,
+                content: required(element, "CONTENT")?.into(),
+                confidence,
+            }))
+        }
+        "SP" => Ok(AltoLineElement::Space(AltoSpace {
+            hpos: number(element, "HPOS")?,
+            vpos: number(element, "VPOS")?,
+            width: number(element, "WIDTH")?,
+        })),
+        "HYP" => Ok(AltoLineElement::Hyphen(AltoHyphen {
+            hpos: number(element, "HPOS")?,
+            vpos: number(element, "VPOS")?,
+            width: number(element, "WIDTH")?,
+            content: required(element, "CONTENT")?.into(),
+        })),
+        name => Err(schema_error(format!(
+            "unexpected element '{}' in TextLine",
+            name
+        ))),
+    }
+}
+
+fn child<'a, 'input>(node: Node<'a, 'input>, name: &'a str) -> Option<Node<'a, 'input>> {
+    children(node, name).next()
+}
+
+fn children<'a, 'input: 'a>(
+    node: Node<'a, 'input>,
+    name: &'a str,
+) -> impl Iterator<Item = Node<'a, 'input>> + 'a {
+    node.children()
+        .filter(move |x| x.is_element() && x.tag_name().name() == name)
+}
+
+fn id(node: Node) -> String {
+    node.attribute("ID").unwrap_or_default().into()
+}
+
+fn required<'a>(node: Node<'a, '_>, attribute: &str) -> TessResult<&'a str> {
+    node.attribute(attribute).ok_or_else(|| {
+        schema_error(format!(
+            "missing {} on {} '{}'",
+            attribute,
+            node.tag_name().name(),
+            id(node)
+        ))
+    })
+}
+
+/// Positions are integers in pixel units but may be fractional in others.
+fn number(node: Node, attribute: &str) -> TessResult<i32> {
+    let value = required(node, attribute)?;
+    value
+        .parse::<f32>()
+        .map(|x| x.round() as i32)
+        .map_err(|_| schema_error(format!("invalid {} '{}'", attribute, value)))
+}
+
+fn bbox(node: Node) -> TessResult<BoundingBox> {
+    let left = number(node, "HPOS")?;
+    let top = number(node, "VPOS")?;
+    Ok(BoundingBox::new(
+        left,
+        top,
+        left + number(node, "WIDTH")?,
+        top + number(node, "HEIGHT")?,
+    ))
+}
+
+fn schema_error(message: String) -> TessError {
+    TessError::SchemaError(format!("ALTO: {}", message))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{output_alto::parse_alto, *};
+
+    const ALTO: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<alto xmlns="http://www.loc.gov/standards/alto/ns-v3#" xmlns:xlink="http://www.w3.org/1999/xlink" xmlns:xsi="http://www.w3.org/2001/XMLSchema-instance" xsi:schemaLocation="http://www.loc.gov/standards/alto/ns-v3# http://www.loc.gov/alto/v3/alto-3-0.xsd">
+	<Description>
+		<MeasurementUnit>pixel</MeasurementUnit>
+		<sourceImageInformation>
+			<fileName>img/string.png</fileName>
+		</sourceImageInformation>
+	</Description>
+	<Layout>
+		<Page WIDTH="696" HEIGHT="89" PHYSICAL_IMG_NR="0" ID="page_0">
+			<PrintSpace HPOS="0" VPOS="0" WIDTH="696" HEIGHT="89">
+				<ComposedBlock ID="cblock_0" HPOS="18" VPOS="29" WIDTH="653" HEIGHT="35">
+					<TextBlock ID="block_0" HPOS="18" VPOS="29" WIDTH="653" HEIGHT="35">
+						<TextLine ID="line_0" HPOS="18" VPOS="29" WIDTH="653" HEIGHT="35">
+							<String ID="string_0" HPOS="18" VPOS="29" WIDTH="144" HEIGHT="35" WC="0.96" CONTENT="LOREM"/><SP WIDTH="19" VPOS="29" HPOS="162"/>
+							<String ID="string_1" HPOS="181" VPOS="29" WIDTH="123" HEIGHT="35" WC="0.92" CONTENT="IPS"/><HYP WIDTH="8" VPOS="29" HPOS="304" CONTENT="-"/>
+						</TextLine>
+					</TextBlock>
+				</ComposedBlock>
+			</PrintSpace>
+		</Page>
+	</Layout>
+</alto>
+"#;
+
+    #[test]
+    fn test_parse_alto() {
+        let (namespace, measurement_unit, pages) = parse_alto(ALTO).unwrap();
+        assert_eq!(namespace, ALTO_NAMESPACES[0]);
+        assert_eq!(measurement_unit, "pixel");
+
+        let page = &pages[0];
+        assert_eq!(
+            (page.id.as_str(), page.width, page.height),
+            ("page_0", 696, 89)
+        );
+
+        let line = &page.print_space.text_blocks[0].text_lines[0];
+        assert_eq!(line.bbox, BoundingBox::new(18, 29, 671, 64));
+        assert_eq!(
+            line.elements,
+            vec![
+                AltoLineElement::String(AltoString {
+                    id: "string_0".into(),
+                    bbox: BoundingBox::new(18, 29, 162, 64),
+                    content: "LOREM".into(),
+                    confidence: Some(0.96),
+                }),
+                AltoLineElement::Space(AltoSpace {
+                    hpos: 162,
+                    vpos: 29,
+                    width: 19
+                }),
+                AltoLineElement::String(AltoString {
+                    id: "string_1".into(),
+                    bbox: BoundingBox::new(181, 29, 304, 64),
+                    content: "IPS".into(),
+                    confidence: Some(0.92),
+                }),
+                AltoLineElement::Hyphen(AltoHyphen {
+                    hpos: 304,
+                    vpos: 29,
+                    width: 8,
+                    content: "-".into()
+                }),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_alto_schema_error() {
+        let result = parse_alto(&ALTO.replace(" CONTENT=\"LOREM\"", ""));
+        assert_eq!(
+            result,
+            Err(TessError::SchemaError(
+                "ALTO: missing CONTENT on String 'string_0'".into()
+            ))
+        );
+
+        let result = parse_alto(&ALTO.replace("ns-v3#", "ns-v2#"));
+        assert!(matches!(result, Err(TessError::SchemaError(_))));
+    }
+
+    #[test]
+    fn test_image_to_alto() {
+        let img = Image::from_path("img/string.png").unwrap();
+        let args = Args {
+            psm: PageSegMode::SingleBlock,
+            ..Default::default()
+        };
+
+        let result = image_to_alto(&img, &args).unwrap();
+        assert_eq!(
+            result
+                .strings()
+                .map(|x| x.content.as_str())
+                .collect::<Vec<_>>(),
+            vec!["LOREM", "IPSUM", "DOLOR", "SIT", "AMET"]
+        );
+    }
+}
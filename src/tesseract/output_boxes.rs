@@ -1,10 +1,23 @@
 use super::*;
 use core::fmt;
 
-#[derive(Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct BoxOutput {
     pub output: String,
     pub boxes: Vec<Box>,
+    pub diagnostics: Vec<Diagnostic>,
+}
+
+impl BoxOutput {
+    pub(crate) fn from_output(output: ProcessOutput) -> TessResult<Self> {
+        let boxes = string_to_boxes(&output.stdout)?;
+        Ok(BoxOutput {
+            output: output.stdout,
+            boxes,
+            diagnostics: output.diagnostics,
+        })
+    }
 }
 
 impl fmt::Display for BoxOutput {
@@ -13,7 +26,8 @@ impl fmt::Display for BoxOutput {
     }
 }
 
-#[derive(Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Box {
     pub symbol: String,
     pub left: i32,
@@ -23,6 +37,18 @@ pub struct Box {
     pub page: i32,
 }
 
+impl Box {
+    /// Box file coordinates count y from the bottom edge, so the image height
+    /// is needed to convert them to a top-left origin.
+    pub fn bbox(&self, image_height: i32) -> BoundingBox {
+        BoundingBox::from_bottom_left(self.left, self.bottom, self.right, self.top, image_height)
+    }
+
+    pub fn set_bbox(&mut self, bbox: BoundingBox, image_height: i32) {
+        (self.left, self.bottom, self.right, self.top) = bbox.to_bottom_left(image_height);
+    }
+}
+
 impl fmt::Display for Box {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(
@@ -48,20 +74,32 @@ impl FromLine for Box {
     }
 }
 
-pub fn image_to_boxes(image: &Image, args: &Args) -> TessResult<BoxOutput> {
-    let mut command = create_tesseract_command(image, args)?;
-    command.arg("makebox");
+impl Tesseract {
+    pub fn image_to_boxes(&self, image: &Image, args: &Args) -> TessResult<BoxOutput> {
+        let upright = self.upright(image, args)?;
+        let image = upright.as_ref().map_or(image, |x| &x.image);
 
-    let output = run_tesseract_command(&mut command)?;
-    let boxes = string_to_boxes(&output)?;
-    Ok(BoxOutput { output, boxes })
+        let mut command = self.create_tesseract_command(image, args)?;
+        command.arg("makebox");
+
+        let output = run_tesseract_command(&mut command, RunOptions::new(image, args))?;
+        let mut output = BoxOutput::from_output(output)?;
+        if let Some(upright) = &upright {
+            output.unrotate(upright);
+        }
+        Ok(output)
+    }
+}
+
+pub fn image_to_boxes(image: &Image, args: &Args) -> TessResult<BoxOutput> {
+    Tesseract::shared().image_to_boxes(image, args)
 }
 
 fn string_to_boxes(output: &str) -> TessResult<Vec<Box>> {
     output
         .lines()
-        .into_iter()
-        .map(|line| Box::parse(line.into()))
+        .enumerate()
+        .map(|(index, line)| Box::parse(index + 1, line))
         .collect::<_>()
 }
 
@@ -86,10 +124,11 @@ mod tests {
     }
 
     #[test]
+    #[allow(clippy::field_reassign_with_default)]
     fn test_image_to_boxes() {
         let img = Image::from_path("img/string.png").unwrap();
         let mut image_to_boxes_args = Args::default();
-        image_to_boxes_args.psm = 6;
+        image_to_boxes_args.psm = PageSegMode::SingleBlock;
 
         let result = image_to_boxes(&img, &image_to_boxes_args).unwrap();
         assert_eq!(
@@ -127,9 +166,10 @@ mod tests {
         let result = string_to_boxes("L 18 X 36 59 0");
         assert_eq!(
             result,
-            Err(TessError::ParseError(
-                "invalid line 'L 18 X 36 59 0'".into()
-            ))
+            Err(TessError::LineParseError {
+                line_number: 1,
+                line: "L 18 X 36 59 0".into()
+            })
         )
     }
 }
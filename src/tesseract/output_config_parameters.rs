@@ -1,25 +1,111 @@
 use super::*;
 use core::fmt;
+use std::collections::BTreeMap;
 
-#[derive(Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ConfigParameterOutput {
     pub output: String,
     pub config_parameters: Vec<ConfigParameter>,
 }
 
+impl ConfigParameterOutput {
+    pub(crate) fn from_output(output: ProcessOutput) -> TessResult<Self> {
+        let output = output.stdout;
+        let config_parameters = string_to_config_parameter_output(&output)?;
+        Ok(ConfigParameterOutput {
+            output,
+            config_parameters,
+        })
+    }
+}
+
+impl ConfigParameterOutput {
+    pub fn get(&self, name: &str) -> Option<&ConfigParameter> {
+        self.config_parameters.iter().find(|x| x.name == name)
+    }
+
+    /// Parameters whose name or description contains `query`, ignoring case.
+    pub fn search(&self, query: &str) -> Vec<&ConfigParameter> {
+        let query = query.to_lowercase();
+        self.config_parameters
+            .iter()
+            .filter(|x| {
+                x.name.to_lowercase().contains(&query)
+                    || x.description.to_lowercase().contains(&query)
+            })
+            .collect()
+    }
+
+    /// Parameters grouped by their prefix family, e.g. `tessedit` or `textord`.
+    pub fn families(&self) -> BTreeMap<&str, Vec<&ConfigParameter>> {
+        let mut families = BTreeMap::<_, Vec<_>>::new();
+        for parameter in &self.config_parameters {
+            families
+                .entry(parameter.family())
+                .or_default()
+                .push(parameter);
+        }
+        families
+    }
+
+    /// Config variables of `args` that differ from tesseract's defaults,
+    /// including variables tesseract does not know.
+    pub fn diff(&self, args: &Args) -> Vec<ConfigOverride> {
+        args.config_variables
+            .iter()
+            .filter_map(|(name, value)| {
+                let default_value = self.get(name).map(|x| x.default_value.clone());
+                match &default_value {
+                    Some(default_value) if default_value.same_as(value) => None,
+                    _ => Some(ConfigOverride {
+                        name: name.clone(),
+                        default_value,
+                        value: value.clone(),
+                    }),
+                }
+            })
+            .collect()
+    }
+}
+
 impl fmt::Display for ConfigParameterOutput {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "{}", self.output)
     }
 }
 
-#[derive(Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ConfigParameter {
     pub name: String,
-    pub default_value: String,
+    /// Default with its type inferred from the printed value and the name,
+    /// see [`ConfigParameter::value_type`].
+    pub default_value: ConfigValue,
     pub description: String,
 }
 
+impl ConfigParameter {
+    /// Type that values for this parameter are checked against.
+    ///
+    /// Tesseract prints booleans as `0`/`1` and whole doubles without a
+    /// fraction, so those defaults are told apart by tesseract's naming
+    /// conventions: names about ratios, penalties or thresholds are doubles,
+    /// names about debug levels, modes or sizes are integers, and the other
+    /// `0`/`1` defaults are flags like `tessedit_create_hocr`. The inference
+    /// can be wrong for unusually named parameters.
+    pub fn value_type(&self) -> ConfigValueType {
+        self.default_value.value_type()
+    }
+
+    /// Name prefix up to the first underscore, e.g. `lstm` for `lstm_choice_mode`.
+    pub fn family(&self) -> &str {
+        self.name
+            .split_once('_')
+            .map_or(self.name.as_str(), |(family, _)| family)
+    }
+}
+
 impl fmt::Display for ConfigParameter {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(
@@ -37,36 +123,111 @@ impl FromLine for ConfigParameter {
 
         Some(ConfigParameter {
             name: name.into(),
-            default_value: default_value.into(),
+            default_value: infer_default(name, default_value),
             description: description.into(),
         })
     }
 }
 
+/// Config variable whose value differs from tesseract's default.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ConfigOverride {
+    pub name: String,
+    /// `None` if tesseract does not know the variable.
+    pub default_value: Option<ConfigValue>,
+    pub value: ConfigValue,
+}
+
+impl fmt::Display for ConfigOverride {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.default_value {
+            Some(default_value) => write!(f, "{} {} -> {}", self.name, default_value, self.value),
+            None => write!(f, "{} (unknown) -> {}", self.name, self.value),
+        }
+    }
+}
+
+impl Tesseract {
+    pub fn get_tesseract_config_parameters(&self) -> TessResult<ConfigParameterOutput> {
+        Self::cached(&self.config_parameters, || {
+            let mut command = self.command();
+            command.arg("--print-parameters");
+
+            let output = run_tesseract_command(&mut command, RunOptions::default())?;
+            ConfigParameterOutput::from_output(output)
+        })
+    }
+
+    /// Validates `args` against this tesseract installation: its config
+    /// variables against the known parameters, and OSD modes against the
+    /// installed languages, since LSTM-only traineddata comes without
+    /// `osd.traineddata`.
+    pub fn validate_args(&self, args: &Args) -> TessResult<()> {
+        args.validate()?;
+        if args.psm.uses_osd() {
+            check_osd_installed(&self.get_tesseract_langs()?)?;
+        }
+        args.validate_config_variables(&self.get_tesseract_config_parameters()?)
+    }
+}
+
 pub fn get_tesseract_config_parameters() -> TessResult<ConfigParameterOutput> {
-    let mut command = get_tesseract_command();
-    command.arg("--print-parameters");
+    Tesseract::shared().get_tesseract_config_parameters()
+}
 
-    let output = run_tesseract_command(&mut command)?;
+/// Name parts of parameters that hold doubles even if the default is whole.
+const DOUBLE_WORDS: &[&str] = &[
+    "certainty",
+    "factor",
+    "frac",
+    "fraction",
+    "multiplier",
+    "penalty",
+    "rating",
+    "ratio",
+    "scale",
+    "thresh",
+    "threshold",
+    "weight",
+];
 
-    let config_parameters = string_to_config_parameter_output(&output)?;
+/// Name parts of integer parameters that may default to `0` or `1`.
+const INT_WORDS: &[&str] = &[
+    "count",
+    "debug",
+    "level",
+    "max",
+    "min",
+    "mode",
+    "num",
+    "number",
+    "parallelize",
+    "size",
+];
 
-    Ok(ConfigParameterOutput {
-        output,
-        config_parameters,
-    })
+fn infer_default(name: &str, value: &str) -> ConfigValue {
+    let has_word = |words: &[&str]| name.split('_').any(|x| words.contains(&x));
+    match ConfigValue::infer(value) {
+        ConfigValue::Int(x) if has_word(DOUBLE_WORDS) => ConfigValue::Double(x as f64),
+        ConfigValue::Int(x @ (0 | 1)) if !has_word(INT_WORDS) => ConfigValue::Bool(x == 1),
+        x => x,
+    }
 }
 
 fn string_to_config_parameter_output(output: &str) -> TessResult<Vec<ConfigParameter>> {
     output
         .lines()
+        .enumerate()
         .skip(1)
-        .map(|line| ConfigParameter::parse(line))
+        .map(|(index, line)| ConfigParameter::parse(index + 1, line))
         .collect::<_>()
 }
 
 #[cfg(test)]
 mod tests {
+    #[cfg(unix)]
+    use crate::tesseract::test_util::FakeTesseract;
     use crate::{output_config_parameters::string_to_config_parameter_output, *};
 
     #[test]
@@ -82,7 +243,7 @@ mod tests {
 
         let expected = ConfigParameter {
             name: "log_level".into(),
-            default_value: "2147483647".into(),
+            default_value: ConfigValue::Int(2147483647),
             description: "Logging level".into(),
         };
 
@@ -107,6 +268,38 @@ mod tests {
         assert_eq!(*x, expected);
     }
 
+    #[test]
+    fn test_infer_default_types() {
+        let parameters = string_to_config_parameter_output(
+            "Tesseract parameters:\n\
+        textord_debug_block\t0\tBlock to do debug on\n\
+        textord_min_xheight\t10\tMin credible pixel xheight\n\
+        tessedit_create_hocr\t0\tWrite .html hOCR output file\n\
+        load_system_dawg\t1\tLoad system word dawg.\n\
+        classify_misfit_junk_penalty\t0\tPenalty to apply when a non-alnum is vertically out of its expected textline position\n\
+        textord_noise_area_ratio\t0.7\tFraction of bounding box for noise\n\
+        tessedit_char_blacklist\t\tBlacklist of chars not to recognize",
+        )
+        .unwrap();
+
+        assert_eq!(
+            parameters
+                .iter()
+                .map(|x| (x.name.as_str(), x.value_type()))
+                .collect::<Vec<_>>(),
+            vec![
+                ("textord_debug_block", ConfigValueType::Int),
+                ("textord_min_xheight", ConfigValueType::Int),
+                ("tessedit_create_hocr", ConfigValueType::Bool),
+                ("load_system_dawg", ConfigValueType::Bool),
+                ("classify_misfit_junk_penalty", ConfigValueType::Double),
+                ("textord_noise_area_ratio", ConfigValueType::Double),
+                ("tessedit_char_blacklist", ConfigValueType::String),
+            ]
+        );
+        assert_eq!(parameters[3].default_value, ConfigValue::Bool(true));
+    }
+
     #[test]
     fn test_string_to_config_parameter_output_parse_error() {
         let result = string_to_config_parameter_output(
@@ -118,7 +311,84 @@ mod tests {
         );
         assert_eq!(
             result,
-            Err(TessError::ParseError("invalid line 'Test'".into()))
+            Err(TessError::LineParseError {
+                line_number: 3,
+                line: "Test".into()
+            })
         )
     }
+
+    #[test]
+    fn test_families_search_and_diff() {
+        let parameters = ConfigParameterOutput::from_output(ProcessOutput {
+            stdout: "Tesseract parameters:\n\
+            textord_min_xheight\t10\tMin credible pixel xheight for AI\n\
+            tessedit_char_whitelist\t\tWhitelist of chars to recognize\n\
+            textord_tabfind_vertical_text\t1\tEnable vertical detection\n\
+            lstm_rating_coefficient\t5\tSets the rating coefficient for the lstm choices"
+                .into(),
+            diagnostics: vec![],
+        })
+        .unwrap();
+
+        let families = parameters.families();
+        assert_eq!(
+            families.keys().copied().collect::<Vec<_>>(),
+            vec!["lstm", "tessedit", "textord"]
+        );
+        assert_eq!(families["textord"].len(), 2);
+
+        let found = parameters.search("WHITELIST");
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].name, "tessedit_char_whitelist");
+        assert_eq!(found[0].value_type(), ConfigValueType::String);
+        assert_eq!(
+            parameters
+                .get("textord_tabfind_vertical_text")
+                .unwrap()
+                .value_type(),
+            ConfigValueType::Bool
+        );
+
+        let args = Args {
+            config_variables: std::collections::BTreeMap::from([
+                ("textord_min_xheight".into(), ConfigValue::Double(10.0)),
+                ("textord_tabfind_vertical_text".into(), false.into()),
+                ("tessedit_char_whitelist".into(), "0123456789".into()),
+                ("made_up".into(), 1.into()),
+            ]),
+            ..Default::default()
+        };
+        let diff = parameters.diff(&args);
+        assert_eq!(
+            diff.iter().map(|x| x.to_string()).collect::<Vec<_>>(),
+            vec![
+                "made_up (unknown) -> 1",
+                "tessedit_char_whitelist  -> 0123456789",
+                "textord_tabfind_vertical_text 1 -> 0",
+            ]
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_validate_args_without_osd() {
+        let fake = FakeTesseract::new(
+            r#"case "$*" in
+                *--list-langs*) printf 'List of available languages (1):\neng\n' ;;
+                *) printf 'Tesseract parameters:\ntextord_min_xheight\t10\tMin credible pixel xheight\n' ;;
+            esac"#,
+        );
+        assert_eq!(fake.tesseract.validate_args(&Args::default()), Ok(()));
+
+        let args = Args {
+            psm: PageSegMode::AutoOsd,
+            ..Default::default()
+        };
+        assert_eq!(args.validate(), Ok(()));
+        assert_eq!(
+            fake.tesseract.validate_args(&args),
+            Err(TessError::MissingTraineddataError("osd".into()))
+        );
+    }
 }
@@ -1,10 +1,23 @@
 use super::*;
 use core::fmt;
 
-#[derive(Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct DataOutput {
     pub output: String,
     pub data: Vec<Data>,
+    pub diagnostics: Vec<Diagnostic>,
+}
+
+impl DataOutput {
+    pub(crate) fn from_output(output: ProcessOutput) -> TessResult<Self> {
+        let data = string_to_data(&output.stdout)?;
+        Ok(DataOutput {
+            output: output.stdout,
+            data,
+            diagnostics: output.diagnostics,
+        })
+    }
 }
 
 impl fmt::Display for DataOutput {
@@ -13,7 +26,8 @@ impl fmt::Display for DataOutput {
     }
 }
 
-#[derive(Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Data {
     pub level: i32,
     pub page_num: i32,
@@ -29,6 +43,30 @@ pub struct Data {
     pub text: String,
 }
 
+impl Data {
+    pub fn bbox(&self) -> BoundingBox {
+        BoundingBox::new(
+            self.left,
+            self.top,
+            self.left + self.width,
+            self.top + self.height,
+        )
+    }
+
+    pub fn set_bbox(&mut self, bbox: BoundingBox) {
+        self.left = bbox.left;
+        self.top = bbox.top;
+        self.width = bbox.width();
+        self.height = bbox.height();
+    }
+}
+
+impl From<&Data> for BoundingBox {
+    fn from(data: &Data) -> Self {
+        data.bbox()
+    }
+}
+
 impl fmt::Display for Data {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(
@@ -70,23 +108,33 @@ impl FromLine for Data {
     }
 }
 
-pub fn image_to_data(image: &Image, args: &Args) -> TessResult<DataOutput> {
-    let mut command = create_tesseract_command(image, args)?;
-    command.arg("tsv");
+impl Tesseract {
+    pub fn image_to_data(&self, image: &Image, args: &Args) -> TessResult<DataOutput> {
+        let upright = self.upright(image, args)?;
+        let image = upright.as_ref().map_or(image, |x| &x.image);
 
-    let output = run_tesseract_command(&mut command)?;
+        let mut command = self.create_tesseract_command(image, args)?;
+        command.arg("tsv");
 
-    let data = string_to_data(&output)?;
+        let output = run_tesseract_command(&mut command, RunOptions::new(image, args))?;
+        let mut output = DataOutput::from_output(output)?;
+        if let Some(upright) = &upright {
+            output.unrotate(upright);
+        }
+        Ok(output)
+    }
+}
 
-    Ok(DataOutput { output, data })
+pub fn image_to_data(image: &Image, args: &Args) -> TessResult<DataOutput> {
+    Tesseract::shared().image_to_data(image, args)
 }
 
 fn string_to_data(output: &str) -> TessResult<Vec<Data>> {
     output
         .lines()
-        .into_iter()
+        .enumerate()
         .skip(1)
-        .map(|line| Data::parse(line.into()))
+        .map(|(index, line)| Data::parse(index + 1, line))
         .collect::<_>()
 }
 
@@ -95,6 +143,7 @@ mod tests {
     use crate::{output_data::string_to_data, *};
 
     #[test]
+    #[allow(clippy::excessive_precision)]
     fn test_string_to_data() {
         let result = string_to_data("level   page_num        block_num       par_num line_num        word_num        left    top     width   height  conf    text
         5       1       1       1       1       1       65      41      46      20      96.063751       The");
@@ -118,10 +167,11 @@ mod tests {
     }
 
     #[test]
+    #[allow(clippy::field_reassign_with_default)]
     fn test_image_to_data() {
         let img = Image::from_path("img/string.png").unwrap();
         let mut image_to_boxes_args = Args::default();
-        image_to_boxes_args.psm = 6;
+        image_to_boxes_args.psm = PageSegMode::SingleBlock;
 
         let result = tesseract::image_to_data(&img, &image_to_boxes_args).unwrap();
         assert_eq!(
@@ -148,7 +198,10 @@ mod tests {
         Test");
         assert_eq!(
             result,
-            Err(TessError::ParseError("invalid line 'Test'".into()))
+            Err(TessError::LineParseError {
+                line_number: 2,
+                line: "Test".into()
+            })
         )
     }
 }
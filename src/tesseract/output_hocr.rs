@@ -0,0 +1,341 @@
+use super::*;
+use core::fmt;
+use roxmltree::Node;
+
+#[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct HocrOutput {
+    pub output: String,
+    pub pages: Vec<HocrPage>,
+    pub diagnostics: Vec<Diagnostic>,
+}
+
+impl HocrOutput {
+    pub(crate) fn from_output(output: ProcessOutput) -> TessResult<Self> {
+        let pages = string_to_hocr_pages(&output.stdout)?;
+        Ok(HocrOutput {
+            output: output.stdout,
+            pages,
+            diagnostics: output.diagnostics,
+        })
+    }
+
+    pub fn words(&self) -> impl Iterator<Item = &HocrWord> {
+        self.pages
+            .iter()
+            .flat_map(|x| &x.areas)
+            .flat_map(|x| &x.paragraphs)
+            .flat_map(|x| &x.lines)
+            .flat_map(|x| &x.words)
+    }
+}
+
+impl fmt::Display for HocrOutput {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.output)
+    }
+}
+
+/// `ocr_page` element.
+#[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct HocrPage {
+    pub id: String,
+    pub bbox: BoundingBox,
+    pub image: Option<String>,
+    pub page_number: Option<u32>,
+    /// Horizontal and vertical scan resolution in dpi.
+    pub scan_res: Option<(u32, u32)>,
+    pub areas: Vec<HocrArea>,
+}
+
+/// `ocr_carea` element.
+#[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct HocrArea {
+    pub id: String,
+    pub bbox: BoundingBox,
+    pub paragraphs: Vec<HocrParagraph>,
+}
+
+/// `ocr_par` element.
+#[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct HocrParagraph {
+    pub id: String,
+    pub bbox: BoundingBox,
+    pub lang: Option<String>,
+    pub lines: Vec<HocrLine>,
+}
+
+/// `ocr_line` element or one of its variants such as `ocr_caption`,
+/// `ocr_header` or `ocr_textfloat`.
+#[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct HocrLine {
+    pub id: String,
+    pub class: String,
+    pub bbox: BoundingBox,
+    /// Slope and constant offset of the baseline relative to the bottom left
+    /// corner of `bbox`.
+    pub baseline: Option<(f32, f32)>,
+    pub x_size: Option<f32>,
+    pub x_descenders: Option<f32>,
+    pub x_ascenders: Option<f32>,
+    pub words: Vec<HocrWord>,
+}
+
+/// `ocrx_word` element.
+#[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct HocrWord {
+    pub id: String,
+    pub bbox: BoundingBox,
+    /// `x_wconf`, between 0 and 100.
+    pub confidence: Option<f32>,
+    pub lang: Option<String>,
+    pub text: String,
+}
+
+const LINE_CLASSES: [&str; 4] = ["ocr_line", "ocr_caption", "ocr_header", "ocr_textfloat"];
+
+impl Tesseract {
+    pub fn image_to_hocr(&self, image: &Image, args: &Args) -> TessResult<HocrOutput> {
+        let mut command = self.create_tesseract_command(image, args)?;
+        command.arg("hocr");
+
+        let output = run_tesseract_command(&mut command, RunOptions::new(image, args))?;
+        HocrOutput::from_output(output)
+    }
+}
+
+pub fn image_to_hocr(image: &Image, args: &Args) -> TessResult<HocrOutput> {
+    Tesseract::shared().image_to_hocr(image, args)
+}
+
+fn string_to_hocr_pages(output: &str) -> TessResult<Vec<HocrPage>> {
+    let options = roxmltree::ParsingOptions {
+        allow_dtd: true,
+        ..Default::default()
+    };
+    let document = roxmltree::Document::parse_with_options(output, options)
+        .map_err(|e| TessError::ParseError(format!("hOCR output: {}", e)))?;
+
+    elements_with_class(document.root(), &["ocr_page"])
+        .map(|page| {
+            let title = Title::parse(page);
+            Ok(HocrPage {
+                id: id(page),
+                bbox: title.bbox(page)?,
+                image: title.get("image").map(|x| x.trim_matches('"').into()),
+                page_number: title.number("ppageno"),
+                scan_res: title
+                    .numbers::<u32>("scan_res")
+                    .and_then(|x| Some((*x.first()?, *x.get(1)?))),
+                areas: elements_with_class(page, &["ocr_carea"])
+                    .map(parse_area)
+                    .collect::<TessResult<_>>()?,
+            })
+        })
+        .collect()
+}
+
+fn parse_area(area: Node) -> TessResult<HocrArea> {
+    Ok(HocrArea {
+        id: id(area),
+        bbox: Title::parse(area).bbox(area)?,
+        paragraphs: elements_with_class(area, &["ocr_par"])
+            .map(|paragraph| {
+                Ok(HocrParagraph {
+                    id: id(paragraph),
+                    bbox: Title::parse(paragraph).bbox(paragraph)?,
+                    lang: lang(paragraph),
+                    lines: elements_with_class(paragraph, &LINE_CLASSES)
+                        .map(parse_line)
+                        .collect::<TessResult<_>>()?,
+                })
+            })
+            .collect::<TessResult<_>>()?,
+    })
+}
+
+fn parse_line(line: Node) -> TessResult<HocrLine> {
+    let title = Title::parse(line);
+    Ok(HocrLine {
+        id: id(line),
+        class: line.attribute("class").unwrap_or_default().into(),
+        bbox: title.bbox(line)?,
+        baseline: title
+            .numbers::<f32>("baseline")
+            .and_then(|x| Some((*x.first()?, *x.get(1)?))),
+        x_size: title.number("x_size"),
+        x_descenders: title.number("x_descenders"),
+        x_ascenders: title.number("x_ascenders"),
+        words: elements_with_class(line, &["ocrx_word"])
+            .map(|word| {
+                let title = Title::parse(word);
+                Ok(HocrWord {
+                    id: id(word),
+                    bbox: title.bbox(word)?,
+                    confidence: title.number("x_wconf"),
+                    lang: lang(word),
+                    text: word
+                        .descendants()
+                        .filter(|x| x.is_text())
+                        .filter_map(|x| x.text())
+                        .collect(),
+                })
+            })
+            .collect::<TessResult<_>>()?,
+    })
+}
+
+fn elements_with_class<'a, 'input: 'a>(
+    node: Node<'a, 'input>,
+    classes: &'a [&str],
+) -> impl Iterator<Item = Node<'a, 'input>> + 'a {
+    node.descendants().filter(move |x| {
+        x.attribute("class")
+            .is_some_and(|class| class.split_whitespace().any(|c| classes.contains(&c)))
+    })
+}
+
+fn id(node: Node) -> String {
+    node.attribute("id").unwrap_or_default().into()
+}
+
+fn lang(node: Node) -> Option<String> {
+    node.attributes()
+        .find(|x| x.name() == "lang")
+        .map(|x| x.value().into())
+}
+
+/// Properties of an hOCR `title` attribute, e.g. `bbox 0 0 10 10; x_wconf 95`.
+struct Title<'a>(Vec<(&'a str, &'a str)>);
+
+impl<'a> Title<'a> {
+    fn parse(node: Node<'a, '_>) -> Self {
+        Title(
+            node.attribute("title")
+                .unwrap_or_default()
+                .split(';')
+                .filter_map(|x| x.trim().split_once(' '))
+                .collect(),
+        )
+    }
+
+    fn get(&self, key: &str) -> Option<&'a str> {
+        self.0.iter().find(|(k, _)| *k == key).map(|(_, v)| *v)
+    }
+
+    fn numbers<T: std::str::FromStr>(&self, key: &str) -> Option<Vec<T>> {
+        self.get(key)?
+            .split_whitespace()
+            .map(|x| x.parse().ok())
+            .collect()
+    }
+
+    fn number<T: std::str::FromStr + Copy>(&self, key: &str) -> Option<T> {
+        self.numbers(key)?.first().copied()
+    }
+
+    fn bbox(&self, node: Node) -> TessResult<BoundingBox> {
+        match self.numbers::<i32>("bbox").as_deref() {
+            Some(&[left, top, right, bottom]) => Ok(BoundingBox::new(left, top, right, bottom)),
+            _ => Err(TessError::ParseError(format!(
+                "hOCR bbox of element '{}'",
+                id(node)
+            ))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{output_hocr::string_to_hocr_pages, *};
+
+    const HOCR: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<!DOCTYPE html PUBLIC "-//W3C//DTD XHTML 1.0 Transitional//EN"
+    "http://www.w3.org/TR/xhtml1/DTD/xhtml1-transitional.dtd">
+<html xmlns="http://www.w3.org/1999/xhtml" xml:lang="en" lang="en">
+ <head>
+  <title></title>
+  <meta http-equiv="Content-Type" content="text/html;charset=utf-8"/>
+  <meta name='ocr-system' content='tesseract 5.3.0' />
+  <meta name='ocr-capabilities' content='ocr_page ocr_carea ocr_par ocr_line ocrx_word ocrp_wconf'/>
+ </head>
+ <body>
+  <div class='ocr_page' id='page_1' title='image "img/string.png"; bbox 0 0 696 89; ppageno 0; scan_res 150 150'>
+   <div class='ocr_carea' id='block_1_1' title="bbox 18 29 671 64">
+    <p class='ocr_par' id='par_1_1' lang='eng' title="bbox 18 29 671 64">
+     <span class='ocr_line' id='line_1_1' title="bbox 18 29 671 64; baseline 0 0; x_size 35; x_descenders 0; x_ascenders 9">
+      <span class='ocrx_word' id='word_1_1' title='bbox 18 29 162 64; x_wconf 95'>LOREM</span>
+      <span class='ocrx_word' id='word_1_2' title='bbox 181 29 304 64; x_wconf 92'><strong>IPSUM</strong></span>
+     </span>
+    </p>
+   </div>
+  </div>
+ </body>
+</html>
+"#;
+
+    #[test]
+    fn test_string_to_hocr_pages() {
+        let pages = string_to_hocr_pages(HOCR).unwrap();
+        assert_eq!(pages.len(), 1);
+
+        let page = &pages[0];
+        assert_eq!(page.image.as_deref(), Some("img/string.png"));
+        assert_eq!(page.bbox, BoundingBox::new(0, 0, 696, 89));
+        assert_eq!(page.page_number, Some(0));
+        assert_eq!(page.scan_res, Some((150, 150)));
+
+        let paragraph = &page.areas[0].paragraphs[0];
+        assert_eq!(paragraph.lang.as_deref(), Some("eng"));
+
+        let line = &paragraph.lines[0];
+        assert_eq!(line.class, "ocr_line");
+        assert_eq!(line.baseline, Some((0.0, 0.0)));
+        assert_eq!(line.x_size, Some(35.0));
+        assert_eq!(line.x_descenders, Some(0.0));
+        assert_eq!(line.x_ascenders, Some(9.0));
+        assert_eq!(
+            line.words[1],
+            HocrWord {
+                id: "word_1_2".into(),
+                bbox: BoundingBox::new(181, 29, 304, 64),
+                confidence: Some(92.0),
+                lang: None,
+                text: "IPSUM".into(),
+            }
+        );
+    }
+
+    #[test]
+    fn test_string_to_hocr_pages_parse_error() {
+        let result = string_to_hocr_pages(
+            "<html><body><div class='ocr_page' id='page_1' title='bbox 0 0'></div></body></html>",
+        );
+        assert_eq!(
+            result,
+            Err(TessError::ParseError(
+                "hOCR bbox of element 'page_1'".into()
+            ))
+        );
+    }
+
+    #[test]
+    fn test_image_to_hocr() {
+        let img = Image::from_path("img/string.png").unwrap();
+        let args = Args {
+            psm: PageSegMode::SingleBlock,
+            ..Default::default()
+        };
+
+        let result = image_to_hocr(&img, &args).unwrap();
+        assert_eq!(
+            result.words().map(|x| x.text.as_str()).collect::<Vec<_>>(),
+            vec!["LOREM", "IPSUM", "DOLOR", "SIT", "AMET"]
+        );
+    }
+}
@@ -0,0 +1,167 @@
+use super::*;
+use core::fmt;
+use std::process::Command;
+
+/// Result of orientation and script detection (`--psm 0`).
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct OsdOutput {
+    pub output: String,
+    pub page_number: i32,
+    /// Clockwise rotation of the input image in degrees (0, 90, 180 or 270).
+    pub orientation: i32,
+    /// Clockwise rotation in degrees that turns the image upright.
+    pub rotate: i32,
+    pub orientation_confidence: f32,
+    pub script: String,
+    pub script_confidence: f32,
+    pub diagnostics: Vec<Diagnostic>,
+}
+
+impl OsdOutput {
+    pub(crate) fn from_output(output: ProcessOutput) -> TessResult<Self> {
+        let mut page_number = None;
+        let mut orientation = None;
+        let mut rotate = None;
+        let mut orientation_confidence = None;
+        let mut script = None;
+        let mut script_confidence = None;
+
+        for (index, line) in output.stdout.lines().enumerate() {
+            let Some((key, value)) = line.split_once(':') else {
+                continue;
+            };
+            let value = value.trim();
+            let invalid = || TessError::LineParseError {
+                line_number: index + 1,
+                line: line.into(),
+            };
+            match key {
+                "Page number" => page_number = Some(value.parse().map_err(|_| invalid())?),
+                "Orientation in degrees" => {
+                    orientation = Some(value.parse().map_err(|_| invalid())?)
+                }
+                "Rotate" => rotate = Some(value.parse().map_err(|_| invalid())?),
+                "Orientation confidence" => {
+                    orientation_confidence = Some(value.parse().map_err(|_| invalid())?)
+                }
+                "Script" => script = Some(value.to_string()),
+                "Script confidence" => {
+                    script_confidence = Some(value.parse().map_err(|_| invalid())?)
+                }
+                _ => {}
+            }
+        }
+
+        let missing = |key: &str| TessError::ParseError(format!("OSD output: missing '{}'", key));
+        Ok(OsdOutput {
+            page_number: page_number.ok_or_else(|| missing("Page number"))?,
+            orientation: orientation.ok_or_else(|| missing("Orientation in degrees"))?,
+            rotate: rotate.ok_or_else(|| missing("Rotate"))?,
+            orientation_confidence: orientation_confidence
+                .ok_or_else(|| missing("Orientation confidence"))?,
+            script: script.ok_or_else(|| missing("Script"))?,
+            script_confidence: script_confidence.ok_or_else(|| missing("Script confidence"))?,
+            output: output.stdout,
+            diagnostics: output.diagnostics,
+        })
+    }
+}
+
+impl fmt::Display for OsdOutput {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.output)
+    }
+}
+
+impl Tesseract {
+    /// Detects orientation and script with `--psm 0`, overriding `args.psm`.
+    /// Fails with [`TessError::MissingTraineddataError`] when `osd.traineddata`
+    /// is not installed.
+    pub fn image_to_osd(&self, image: &Image, args: &Args) -> TessResult<OsdOutput> {
+        check_osd_installed(&self.get_tesseract_langs()?)?;
+        let mut command = self.create_osd_command(image, args)?;
+
+        let output = run_tesseract_command(&mut command, RunOptions::new(image, args))?;
+        OsdOutput::from_output(output)
+    }
+
+    pub(crate) fn create_osd_command(&self, image: &Image, args: &Args) -> TessResult<Command> {
+        let args = Args {
+            psm: PageSegMode::OsdOnly,
+            ..args.clone()
+        };
+        self.create_tesseract_command(image, &args)
+    }
+}
+
+pub fn image_to_osd(image: &Image, args: &Args) -> TessResult<OsdOutput> {
+    Tesseract::shared().image_to_osd(image, args)
+}
+
+pub(crate) fn check_osd_installed(langs: &[String]) -> TessResult<()> {
+    if !langs.iter().any(|x| x == "osd") {
+        return Err(TessError::MissingTraineddataError("osd".into()));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(unix)]
+    use crate::tesseract::test_util::FakeTesseract;
+    use crate::*;
+
+    #[test]
+    fn test_osd_from_output() {
+        let output = OsdOutput::from_output(ProcessOutput {
+            stdout: "Page number: 0\n\
+                Orientation in degrees: 270\n\
+                Rotate: 90\n\
+                Orientation confidence: 9.58\n\
+                Script: Latin\n\
+                Script confidence: 2.25\n"
+                .into(),
+            diagnostics: vec![],
+        })
+        .unwrap();
+        assert_eq!(
+            (output.page_number, output.orientation, output.rotate),
+            (0, 270, 90)
+        );
+        assert_eq!(output.orientation_confidence, 9.58);
+        assert_eq!(output.script, "Latin");
+        assert_eq!(output.script_confidence, 2.25);
+
+        let error = OsdOutput::from_output(ProcessOutput {
+            stdout: "Page number: 0\nRotate: ninety\n".into(),
+            diagnostics: vec![],
+        });
+        assert_eq!(
+            error,
+            Err(TessError::LineParseError {
+                line_number: 2,
+                line: "Rotate: ninety".into()
+            })
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_osd_missing_traineddata() {
+        let fake = FakeTesseract::new("printf 'List of available languages (1):\\neng\\n'");
+        let img = Image::from_path("img/string.png").unwrap();
+        assert_eq!(
+            fake.tesseract.image_to_osd(&img, &Args::default()),
+            Err(TessError::MissingTraineddataError("osd".into()))
+        );
+    }
+
+    #[test]
+    fn test_image_to_osd() {
+        let img = Image::from_path("img/string.png").unwrap();
+        let output = image_to_osd(&img, &Args::default()).unwrap();
+        assert_eq!(output.rotate, 0);
+        assert_eq!(output.script, "Latin");
+    }
+}
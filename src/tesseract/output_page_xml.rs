@@ -0,0 +1,442 @@
+use super::*;
+use core::fmt;
+use roxmltree::Node;
+use std::process::Command;
+
+/// First tesseract release with the PAGE XML renderer.
+pub const PAGE_XML_MIN_VERSION: TesseractVersion = TesseractVersion::new(5, 3, 0);
+
+const PAGE_NAMESPACE_PREFIX: &str = "http://schema.primaresearch.org/PAGE/gts/pagecontent/";
+
+#[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PageXmlOutput {
+    pub output: String,
+    pub pages: Vec<PageXmlPage>,
+    pub diagnostics: Vec<Diagnostic>,
+}
+
+impl PageXmlOutput {
+    pub(crate) fn from_output(output: ProcessOutput) -> TessResult<Self> {
+        Ok(PageXmlOutput {
+            pages: string_to_page_xml_pages(&output.stdout)?,
+            output: output.stdout,
+            diagnostics: output.diagnostics,
+        })
+    }
+
+    /// Words of all text lines, following the reading order of the regions.
+    pub fn words(&self) -> impl Iterator<Item = &PageXmlWord> {
+        self.pages
+            .iter()
+            .flat_map(|x| x.regions_in_reading_order())
+            .flat_map(|x| x.lines())
+            .flat_map(|x| &x.words)
+    }
+}
+
+impl fmt::Display for PageXmlOutput {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.output)
+    }
+}
+
+#[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PageXmlPage {
+    pub image_filename: String,
+    pub image_width: i32,
+    pub image_height: i32,
+    /// Region ids in reading order.
+    pub reading_order: Vec<String>,
+    pub regions: Vec<PageXmlRegion>,
+}
+
+impl PageXmlPage {
+    /// Top-level regions sorted by the page's reading order. Regions the
+    /// reading order does not mention follow in document order.
+    pub fn regions_in_reading_order(&self) -> Vec<&PageXmlRegion> {
+        let mut regions: Vec<_> = self.regions.iter().collect();
+        regions.sort_by_key(|region| {
+            self.reading_order
+                .iter()
+                .position(|id| *id == region.id)
+                .unwrap_or(usize::MAX)
+        });
+        regions
+    }
+}
+
+#[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PageXmlRegion {
+    pub id: String,
+    /// Element name, e.g. `TextRegion`, `ImageRegion` or `TableRegion`.
+    pub kind: String,
+    pub coords: Vec<Point>,
+    /// Regions nested in this one, such as the cells of a table.
+    pub regions: Vec<PageXmlRegion>,
+    pub lines: Vec<PageXmlLine>,
+    pub text: Option<String>,
+}
+
+impl PageXmlRegion {
+    pub fn bbox(&self) -> Option<BoundingBox> {
+        BoundingBox::enclosing(&self.coords)
+    }
+
+    /// Lines of this region followed by those of its nested regions.
+    pub fn lines(&self) -> Vec<&PageXmlLine> {
+        let mut lines: Vec<_> = self.lines.iter().collect();
+        lines.extend(self.regions.iter().flat_map(|x| x.lines()));
+        lines
+    }
+}
+
+#[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PageXmlLine {
+    pub id: String,
+    pub coords: Vec<Point>,
+    pub baseline: Option<Vec<Point>>,
+    pub words: Vec<PageXmlWord>,
+    pub text: Option<String>,
+}
+
+impl PageXmlLine {
+    pub fn bbox(&self) -> Option<BoundingBox> {
+        BoundingBox::enclosing(&self.coords)
+    }
+}
+
+#[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PageXmlWord {
+    pub id: String,
+    pub coords: Vec<Point>,
+    pub text: Option<String>,
+    /// Confidence of the text between 0 and 1.
+    pub confidence: Option<f32>,
+}
+
+impl PageXmlWord {
+    pub fn bbox(&self) -> Option<BoundingBox> {
+        BoundingBox::enclosing(&self.coords)
+    }
+}
+
+impl Tesseract {
+    /// Requires tesseract 5.3 or newer, older versions fail with
+    /// [`TessError::UnsupportedVersionError`] before recognition starts.
+    pub fn image_to_page_xml(&self, image: &Image, args: &Args) -> TessResult<PageXmlOutput> {
+        self.get_tesseract_version_number()?
+            .require(PAGE_XML_MIN_VERSION, "PAGE XML")?;
+        let mut command = self.create_page_xml_command(image, args)?;
+
+        let output = run_tesseract_command(&mut command, RunOptions::new(image, args))?;
+        PageXmlOutput::from_output(output)
+    }
+
+    pub(crate) fn create_page_xml_command(
+        &self,
+        image: &Image,
+        args: &Args,
+    ) -> TessResult<Command> {
+        let mut command = self.create_tesseract_command(image, args)?;
+        command.arg("page");
+        Ok(command)
+    }
+}
+
+pub fn image_to_page_xml(image: &Image, args: &Args) -> TessResult<PageXmlOutput> {
+    Tesseract::shared().image_to_page_xml(image, args)
+}
+
+fn string_to_page_xml_pages(output: &str) -> TessResult<Vec<PageXmlPage>> {
+    let document = roxmltree::Document::parse(output)
+        .map_err(|e| TessError::ParseError(format!("PAGE XML output: {}", e)))?;
+
+    let root = document.root_element();
+    let namespace = root.tag_name().namespace().unwrap_or_default();
+    if root.tag_name().name() != "PcGts" || !namespace.starts_with(PAGE_NAMESPACE_PREFIX) {
+        return Err(schema_error("root element is not a PAGE 'PcGts'".into()));
+    }
+
+    children(root, "Page")
+        .map(|page| {
+            Ok(PageXmlPage {
+                image_filename: required(page, "imageFilename")?.into(),
+                image_width: number(page, "imageWidth")?,
+                image_height: number(page, "imageHeight")?,
+                reading_order: child(page, "ReadingOrder")
+                    .map(reading_order)
+                    .unwrap_or_default(),
+                regions: regions(page)?,
+            })
+        })
+        .collect()
+}
+
+/// Flattens the possibly nested ordered and unordered groups into region ids.
+fn reading_order(group: Node) -> Vec<String> {
+    let mut members: Vec<_> = group.children().filter(|x| x.is_element()).collect();
+    if group.tag_name().name().starts_with("OrderedGroup") {
+        members.sort_by_key(|x| {
+            x.attribute("index")
+                .and_then(|x| x.parse::<usize>().ok())
+                .unwrap_or(usize::MAX)
+        });
+    }
+
+    members
+        .into_iter()
+        .flat_map(|member| {
+            let mut ids: Vec<String> = member
+                .attribute("regionRef")
+                .map(Into::into)
+                .into_iter()
+                .collect();
+            if member.tag_name().name().contains("Group") {
+                ids.extend(reading_order(member));
+            }
+            ids
+        })
+        .collect()
+}
+
+fn regions(parent: Node) -> TessResult<Vec<PageXmlRegion>> {
+    parent
+        .children()
+        .filter(|x| x.is_element() && x.tag_name().name().ends_with("Region"))
+        .map(|region| {
+            Ok(PageXmlRegion {
+                id: required(region, "id")?.into(),
+                kind: region.tag_name().name().into(),
+                coords: coords(region)?,
+                regions: regions(region)?,
+                lines: children(region, "TextLine")
+                    .map(parse_line)
+                    .collect::<TessResult<_>>()?,
+                text: text(region),
+            })
+        })
+        .collect()
+}
+
+fn parse_line(line: Node) -> TessResult<PageXmlLine> {
+    Ok(PageXmlLine {
+        id: required(line, "id")?.into(),
+        coords: coords(line)?,
+        baseline: child(line, "Baseline")
+            .map(|x| points(required(x, "points")?))
+            .transpose()?,
+        words: children(line, "Word")
+            .map(|word| {
+                Ok(PageXmlWord {
+                    id: required(word, "id")?.into(),
+                    coords: coords(word)?,
+                    text: text(word),
+                    confidence: child(word, "TextEquiv")
+                        .and_then(|x| x.attribute("conf"))
+                        .and_then(|x| x.parse().ok()),
+                })
+            })
+            .collect::<TessResult<_>>()?,
+        text: text(line),
+    })
+}
+
+fn coords(node: Node) -> TessResult<Vec<Point>> {
+    let coords = child(node, "Coords").ok_or_else(|| {
+        schema_error(format!(
+            "missing Coords in {} '{}'",
+            node.tag_name().name(),
+            node.attribute("id").unwrap_or_default()
+        ))
+    })?;
+    points(required(coords, "points")?)
+}
+
+/// Parses a `points` attribute such as `18,29 671,29 671,64 18,64`.
+fn points(value: &str) -> TessResult<Vec<Point>> {
+    value
+        .split_whitespace()
+        .map(|point| {
+            point
+                .split_once(',')
+                .and_then(|(x, y)| Some(Point::new(x.parse().ok()?, y.parse().ok()?)))
+                .ok_or_else(|| schema_error(format!("invalid point '{}'", point)))
+        })
+        .collect()
+}
+
+fn text(node: Node) -> Option<String> {
+    child(node, "TextEquiv")
+        .and_then(|x| child(x, "Unicode"))
+        .map(|x| x.text().unwrap_or_default().into())
+}
+
+fn child<'a, 'input>(node: Node<'a, 'input>, name: &'a str) -> Option<Node<'a, 'input>> {
+    children(node, name).next()
+}
+
+fn children<'a, 'input: 'a>(
+    node: Node<'a, 'input>,
+    name: &'a str,
+) -> impl Iterator<Item = Node<'a, 'input>> + 'a {
+    node.children()
+        .filter(move |x| x.is_element() && x.tag_name().name() == name)
+}
+
+fn required<'a>(node: Node<'a, '_>, attribute: &str) -> TessResult<&'a str> {
+    node.attribute(attribute).ok_or_else(|| {
+        schema_error(format!(
+            "missing {} on {}",
+            attribute,
+            node.tag_name().name()
+        ))
+    })
+}
+
+fn number(node: Node, attribute: &str) -> TessResult<i32> {
+    let value = required(node, attribute)?;
+    value
+        .parse()
+        .map_err(|_| schema_error(format!("invalid {} '{}'", attribute, value)))
+}
+
+fn schema_error(message: String) -> TessError {
+    TessError::SchemaError(format!("PAGE XML: {}", message))
+}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(unix)]
+    use crate::tesseract::test_util::FakeTesseract;
+    use crate::{output_page_xml::string_to_page_xml_pages, *};
+
+    const PAGE_XML: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<PcGts xmlns="http://schema.primaresearch.org/PAGE/gts/pagecontent/2019-07-15" xmlns:xsi="http://www.w3.org/2001/XMLSchema-instance" xsi:schemaLocation="http://schema.primaresearch.org/PAGE/gts/pagecontent/2019-07-15 http://schema.primaresearch.org/PAGE/gts/pagecontent/2019-07-15/pagecontent.xsd">
+	<Metadata>
+		<Creator>Tesseract - 5.3.0</Creator>
+	</Metadata>
+	<Page imageFilename="img/string.png" imageWidth="696" imageHeight="89">
+		<ReadingOrder>
+			<OrderedGroup id="ro_1" caption="Regions reading order">
+				<RegionRefIndexed index="1" regionRef="r_1_1"/>
+				<RegionRefIndexed index="0" regionRef="r_1_2"/>
+			</OrderedGroup>
+		</ReadingOrder>
+		<TextRegion id="r_1_1" custom="readingOrder {index:1;}">
+			<Coords points="18,29 671,29 671,64 18,64"/>
+			<TextLine id="r_1_1_tl_1" custom="readingOrder {index:0;}">
+				<Coords points="18,29 671,29 671,64 18,64"/>
+				<Baseline points="18,64 671,64"/>
+				<Word id="r_1_1_tl_1_w_1" custom="readingOrder {index:0;}">
+					<Coords points="18,29 162,29 162,64 18,64"/>
+					<TextEquiv index="1" conf="0.96">
+						<Unicode>LOREM</Unicode>
+					</TextEquiv>
+				</Word>
+				<TextEquiv index="1">
+					<Unicode>LOREM</Unicode>
+				</TextEquiv>
+			</TextLine>
+		</TextRegion>
+		<ImageRegion id="r_1_2">
+			<Coords points="0,0 10,0 10,10 0,10"/>
+		</ImageRegion>
+	</Page>
+</PcGts>
+"#;
+
+    #[test]
+    fn test_string_to_page_xml_pages() {
+        let pages = string_to_page_xml_pages(PAGE_XML).unwrap();
+        let page = &pages[0];
+        assert_eq!(
+            (
+                page.image_filename.as_str(),
+                page.image_width,
+                page.image_height
+            ),
+            ("img/string.png", 696, 89)
+        );
+        assert_eq!(page.reading_order, vec!["r_1_2", "r_1_1"]);
+        assert_eq!(
+            page.regions_in_reading_order()
+                .iter()
+                .map(|x| x.kind.as_str())
+                .collect::<Vec<_>>(),
+            vec!["ImageRegion", "TextRegion"]
+        );
+
+        let line = &page.regions[0].lines[0];
+        assert_eq!(line.bbox(), Some(BoundingBox::new(18, 29, 671, 64)));
+        assert_eq!(
+            line.baseline,
+            Some(vec![Point::new(18, 64), Point::new(671, 64)])
+        );
+        assert_eq!(
+            line.words,
+            vec![PageXmlWord {
+                id: "r_1_1_tl_1_w_1".into(),
+                coords: vec![
+                    Point::new(18, 29),
+                    Point::new(162, 29),
+                    Point::new(162, 64),
+                    Point::new(18, 64)
+                ],
+                text: Some("LOREM".into()),
+                confidence: Some(0.96),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_string_to_page_xml_pages_schema_error() {
+        let result = string_to_page_xml_pages(&PAGE_XML.replace(" imageWidth=\"696\"", ""));
+        assert!(matches!(result, Err(TessError::SchemaError(_))));
+
+        let result = string_to_page_xml_pages(&PAGE_XML.replace("18,29 162,29", "18;29 162,29"));
+        assert_eq!(
+            result,
+            Err(TessError::SchemaError(
+                "PAGE XML: invalid point '18;29'".into()
+            ))
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_page_xml_unsupported_version() {
+        let fake = FakeTesseract::new("echo 'tesseract 5.2.0'");
+        let img = Image::from_path("img/string.png").unwrap();
+        assert_eq!(
+            fake.tesseract.image_to_page_xml(&img, &Args::default()),
+            Err(TessError::UnsupportedVersionError {
+                feature: "PAGE XML".into(),
+                required: PAGE_XML_MIN_VERSION,
+                found: TesseractVersion::new(5, 2, 0),
+            })
+        );
+    }
+
+    #[test]
+    fn test_image_to_page_xml() {
+        let img = Image::from_path("img/string.png").unwrap();
+        let args = Args {
+            psm: PageSegMode::SingleBlock,
+            ..Default::default()
+        };
+
+        let result = image_to_page_xml(&img, &args).unwrap();
+        assert_eq!(
+            result
+                .words()
+                .filter_map(|x| x.text.as_deref())
+                .collect::<Vec<_>>(),
+            vec!["LOREM", "IPSUM", "DOLOR", "SIT", "AMET"]
+        );
+    }
+}
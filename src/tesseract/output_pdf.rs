@@ -0,0 +1,199 @@
+use super::*;
+use std::io::Write;
+use std::process::Command;
+use tempfile::NamedTempFile;
+
+/// Settings of tesseract's `pdf` renderer.
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct PdfOptions {
+    /// Leaves the page images out so the PDF only carries the invisible text
+    /// layer (`textonly_pdf`).
+    pub text_only: bool,
+    /// Quality from 1 to 100 for page images tesseract re-encodes as JPEG
+    /// (`jpg_quality`). Tesseract uses 85 when unset.
+    pub jpg_quality: Option<u8>,
+}
+
+#[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PdfOutput {
+    /// The searchable PDF document.
+    pub output: Vec<u8>,
+    pub diagnostics: Vec<Diagnostic>,
+}
+
+impl PdfOutput {
+    pub(crate) fn from_output(output: ProcessOutput<Vec<u8>>) -> Self {
+        PdfOutput {
+            output: output.stdout,
+            diagnostics: output.diagnostics,
+        }
+    }
+}
+
+impl From<PdfOutput> for Vec<u8> {
+    fn from(output: PdfOutput) -> Self {
+        output.output
+    }
+}
+
+impl Tesseract {
+    pub fn image_to_pdf(
+        &self,
+        image: &Image,
+        args: &Args,
+        options: &PdfOptions,
+    ) -> TessResult<PdfOutput> {
+        self.images_to_pdf(std::slice::from_ref(image), args, options)
+    }
+
+    /// Recognizes all images in a single tesseract run and combines them into
+    /// one PDF with a page per image.
+    pub fn images_to_pdf(
+        &self,
+        images: &[Image],
+        args: &Args,
+        options: &PdfOptions,
+    ) -> TessResult<PdfOutput> {
+        let input = PdfInput::new(images)?;
+        let mut command = self.create_pdf_command(&input, args, options)?;
+
+        let output = run_tesseract_command_bytes(&mut command, RunOptions::from_args(args))?;
+        Ok(PdfOutput::from_output(output))
+    }
+
+    pub(crate) fn create_pdf_command(
+        &self,
+        input: &PdfInput,
+        args: &Args,
+        options: &PdfOptions,
+    ) -> TessResult<Command> {
+        let mut command = self.create_tesseract_command_for_input(input.get_input_arg()?, args)?;
+
+        if options.text_only {
+            command.arg("-c").arg("textonly_pdf=1");
+        }
+        if let Some(quality) = options.jpg_quality {
+            if !(1..=100).contains(&quality) {
+                return Err(TessError::ArgumentError(format!(
+                    "jpg_quality must be between 1 and 100, got {}",
+                    quality
+                )));
+            }
+            command.arg("-c").arg(format!("jpg_quality={}", quality));
+        }
+        command.arg("pdf");
+
+        Ok(command)
+    }
+}
+
+pub fn image_to_pdf(image: &Image, args: &Args, options: &PdfOptions) -> TessResult<PdfOutput> {
+    Tesseract::shared().image_to_pdf(image, args, options)
+}
+
+pub fn images_to_pdf(images: &[Image], args: &Args, options: &PdfOptions) -> TessResult<PdfOutput> {
+    Tesseract::shared().images_to_pdf(images, args, options)
+}
+
+/// Input of a `pdf` run. The renderer embeds the original image file, so
+/// images are always passed by path and never through stdin.
+pub(crate) enum PdfInput<'a> {
+    Image(&'a str),
+    /// Text file listing one image path per line, which tesseract processes
+    /// as consecutive pages.
+    List(NamedTempFile),
+}
+
+impl<'a> PdfInput<'a> {
+    pub fn new(images: &'a [Image]) -> TessResult<Self> {
+        match images {
+            [] => Err(TessError::ArgumentError(
+                "at least one image is required for a PDF".into(),
+            )),
+            [image] => Ok(PdfInput::Image(image.get_image_path()?)),
+            _ => {
+                let mut list = tempfile::Builder::new()
+                    .prefix("rusty-tesseract")
+                    .suffix(".txt")
+                    .tempfile()
+                    .map_err(|e| TessError::TempfileError(e.to_string()))?;
+                for image in images {
+                    writeln!(list, "{}", image.get_image_path()?)
+                        .map_err(|e| TessError::TempfileError(e.to_string()))?;
+                }
+                Ok(PdfInput::List(list))
+            }
+        }
+    }
+
+    fn get_input_arg(&self) -> TessResult<&str> {
+        match self {
+            PdfInput::Image(path) => Ok(path),
+            PdfInput::List(list) => list
+                .path()
+                .to_str()
+                .ok_or_else(|| TessError::TempfileError("path is not valid UTF-8".into())),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(unix)]
+    use crate::tesseract::test_util::FakeTesseract;
+    use crate::*;
+
+    #[cfg(unix)]
+    #[test]
+    fn test_images_to_pdf() {
+        // succeeds only for a two page list with both options, printing bytes that are not UTF-8
+        let fake = FakeTesseract::new(
+            r#"test "$(wc -l < "$1")" -eq 2 || exit 1
+case "$*" in
+    *"textonly_pdf=1"*"jpg_quality=60 pdf") printf '%%PDF-1.5\n\342\343\n' ;;
+    *) exit 2 ;;
+esac"#,
+        );
+        let images = [
+            Image::from_path("img/string.png").unwrap(),
+            Image::from_path("img/string.png").unwrap(),
+        ];
+        let options = PdfOptions {
+            text_only: true,
+            jpg_quality: Some(60),
+        };
+
+        let output = fake
+            .tesseract
+            .images_to_pdf(&images, &Args::default(), &options)
+            .unwrap();
+        assert_eq!(output.output, b"%PDF-1.5\n\xe2\xe3\n");
+    }
+
+    #[test]
+    fn test_pdf_argument_errors() {
+        let img = Image::from_path("img/string.png").unwrap();
+        let options = PdfOptions {
+            jpg_quality: Some(0),
+            ..Default::default()
+        };
+        assert!(matches!(
+            image_to_pdf(&img, &Args::default(), &options),
+            Err(TessError::ArgumentError(_))
+        ));
+        assert!(matches!(
+            images_to_pdf(&[], &Args::default(), &PdfOptions::default()),
+            Err(TessError::ArgumentError(_))
+        ));
+    }
+
+    #[test]
+    fn test_image_to_pdf() {
+        let img = Image::from_path("img/string.png").unwrap();
+        let output = image_to_pdf(&img, &Args::default(), &PdfOptions::default()).unwrap();
+        assert!(output.output.starts_with(b"%PDF"));
+    }
+}
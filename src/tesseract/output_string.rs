@@ -0,0 +1,92 @@
+use super::*;
+use core::fmt;
+use std::ops::Deref;
+
+#[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct StringOutput {
+    pub output: String,
+    pub diagnostics: Vec<Diagnostic>,
+}
+
+impl StringOutput {
+    pub(crate) fn from_output(output: ProcessOutput) -> Self {
+        StringOutput {
+            output: output.stdout,
+            diagnostics: output.diagnostics,
+        }
+    }
+}
+
+impl fmt::Display for StringOutput {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.output)
+    }
+}
+
+impl Deref for StringOutput {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        &self.output
+    }
+}
+
+impl From<StringOutput> for String {
+    fn from(output: StringOutput) -> Self {
+        output.output
+    }
+}
+
+impl Tesseract {
+    pub fn image_to_string(&self, image: &Image, args: &Args) -> TessResult<StringOutput> {
+        let upright = self.upright(image, args)?;
+        let image = upright.as_ref().map_or(image, |x| &x.image);
+
+        let mut command = self.create_tesseract_command(image, args)?;
+        let output = run_tesseract_command(&mut command, RunOptions::new(image, args))?;
+
+        Ok(StringOutput::from_output(output))
+    }
+}
+
+pub fn image_to_string(image: &Image, args: &Args) -> TessResult<StringOutput> {
+    Tesseract::shared().image_to_string(image, args)
+}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(unix)]
+    use crate::tesseract::test_util::FakeTesseract;
+    use crate::*;
+
+    #[cfg(unix)]
+    #[test]
+    fn test_image_to_string_diagnostics() {
+        let fake = FakeTesseract::new("echo 'Empty page!!' >&2");
+        let img = Image::from_path("img/string.png").unwrap();
+
+        let result = fake.tesseract.image_to_string(&img, &Args::default());
+        assert_eq!(
+            result,
+            Ok(StringOutput {
+                output: "".into(),
+                diagnostics: vec![Diagnostic::EmptyPage],
+            })
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_image_to_string_via_stdin() {
+        let fake = FakeTesseract::new("echo \"$1 $(wc -c | tr -d ' ')\"");
+        let dynamic_image = image::open("img/string.png").unwrap();
+        let img = Image::from_dynamic_image_via_stdin(&dynamic_image, StdinFormat::Pnm).unwrap();
+
+        let result = fake.tesseract.image_to_string(&img, &Args::default());
+        assert_eq!(
+            result.unwrap().trim(),
+            format!("stdin {}", img.get_stdin_data().unwrap().len())
+        );
+    }
+}
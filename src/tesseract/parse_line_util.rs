@@ -9,7 +9,11 @@ pub(crate) fn parse_next<T: std::str::FromStr>(
 pub(crate) trait FromLine: Sized {
     fn from_line(line: &str) -> Option<Self>;
 
-    fn parse(line: &str) -> TessResult<Self> {
-        Self::from_line(line).ok_or(TessError::ParseError(format!("invalid line '{}'", line)))
+    /// Parses `line`, reporting the 1-based `line_number` on failure.
+    fn parse(line_number: usize, line: &str) -> TessResult<Self> {
+        Self::from_line(line).ok_or_else(|| TessError::LineParseError {
+            line_number,
+            line: line.into(),
+        })
     }
 }
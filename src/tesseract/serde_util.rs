@@ -0,0 +1,251 @@
+//! Serde implementations for types whose JSON shape differs from the derived
+//! one.
+
+use super::*;
+use serde::{de::Error, Deserialize, Deserializer, Serialize, Serializer};
+use std::{io, str::FromStr, time::Duration};
+
+/// [`Duration`] as fractional seconds, e.g. `2.5`.
+pub(crate) mod seconds {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(duration: &Duration, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_f64(duration.as_secs_f64())
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Duration, D::Error> {
+        let seconds = f64::deserialize(deserializer)?;
+        Duration::try_from_secs_f64(seconds).map_err(D::Error::custom)
+    }
+}
+
+/// Optional [`Duration`] as fractional seconds or `null`.
+pub(crate) mod option_seconds {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(
+        duration: &Option<Duration>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        duration.map(|x| x.as_secs_f64()).serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Option<Duration>, D::Error> {
+        Option::<f64>::deserialize(deserializer)?
+            .map(|x| Duration::try_from_secs_f64(x).map_err(D::Error::custom))
+            .transpose()
+    }
+}
+
+/// Modes are written as their numeric value and read from either the value
+/// or a name accepted by `FromStr`.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum ModeValue {
+    Number(i32),
+    Name(String),
+}
+
+fn deserialize_mode<'de, D, T>(deserializer: D) -> Result<T, D::Error>
+where
+    D: Deserializer<'de>,
+    T: TryFrom<i32, Error = TessError> + FromStr<Err = TessError>,
+{
+    match ModeValue::deserialize(deserializer)? {
+        ModeValue::Number(x) => T::try_from(x).map_err(D::Error::custom),
+        ModeValue::Name(x) => x.parse().map_err(D::Error::custom),
+    }
+}
+
+impl Serialize for PageSegMode {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_i32(self.value())
+    }
+}
+
+impl<'de> Deserialize<'de> for PageSegMode {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserialize_mode(deserializer)
+    }
+}
+
+impl Serialize for OcrEngineMode {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_i32(self.value())
+    }
+}
+
+impl<'de> Deserialize<'de> for OcrEngineMode {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserialize_mode(deserializer)
+    }
+}
+
+/// Version as a string like `"5.3.0"`.
+impl Serialize for TesseractVersion {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for TesseractVersion {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer)?
+            .parse()
+            .map_err(D::Error::custom)
+    }
+}
+
+/// Only the message is kept; deserialized errors are of kind
+/// [`io::ErrorKind::Other`].
+impl Serialize for IoError {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for IoError {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Ok(io::Error::other(String::deserialize(deserializer)?).into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+    use serde_json::json;
+    use std::{collections::BTreeMap, time::Duration};
+
+    #[test]
+    fn test_args_json() {
+        let args = Args {
+            config_variables: BTreeMap::from([
+                ("tessedit_char_whitelist".into(), "ABC".into()),
+                ("textord_heavy_nr".into(), true.into()),
+            ]),
+            psm: PageSegMode::SingleBlock,
+            timeout: Some(Duration::from_millis(2500)),
+            cancellation: Some(CancellationToken::new()),
+            ..Default::default()
+        };
+        let value = serde_json::to_value(&args).unwrap();
+        assert_eq!(
+            value,
+            json!({
+                "lang": "eng",
+                "config_variables": {
+                    "tessedit_char_whitelist": "ABC",
+                    "textord_heavy_nr": true
+                },
+                "dpi": 150,
+                "psm": 6,
+                "oem": 3,
+                "timeout": 2.5,
+                "auto_rotate": false
+            })
+        );
+        assert_eq!(
+            serde_json::from_value::<Args>(value).unwrap(),
+            Args {
+                cancellation: None,
+                ..args
+            }
+        );
+
+        let profile: Args =
+            serde_json::from_value(json!({"psm": "single_line", "oem": 1, "dpi": 300})).unwrap();
+        assert_eq!(
+            profile,
+            Args {
+                psm: PageSegMode::SingleLine,
+                oem: OcrEngineMode::LstmOnly,
+                dpi: 300,
+                ..Default::default()
+            }
+        );
+        assert!(serde_json::from_value::<Args>(json!({"psm": "sideways"})).is_err());
+        assert!(serde_json::from_value::<Args>(json!({"psm": 99})).is_err());
+    }
+
+    #[test]
+    fn test_output_json() {
+        let output = BoxOutput {
+            output: "L 18 26 36 59 0\n".into(),
+            boxes: vec![Box {
+                symbol: "L".into(),
+                left: 18,
+                bottom: 26,
+                right: 36,
+                top: 59,
+                page: 0,
+            }],
+            diagnostics: vec![Diagnostic::EmptyPage, Diagnostic::EstimatedResolution(284)],
+        };
+        let value = serde_json::to_value(&output).unwrap();
+        assert_eq!(
+            value,
+            json!({
+                "output": "L 18 26 36 59 0\n",
+                "boxes": [{"symbol": "L", "left": 18, "bottom": 26, "right": 36, "top": 59, "page": 0}],
+                "diagnostics": ["empty_page", {"estimated_resolution": 284}]
+            })
+        );
+        assert_eq!(serde_json::from_value::<BoxOutput>(value).unwrap(), output);
+
+        let parameter = ConfigParameter {
+            name: "tessedit_pageseg_mode".into(),
+            default_value: ConfigValue::Int(6),
+            description: "Page seg mode".into(),
+        };
+        let value = serde_json::to_value(&parameter).unwrap();
+        assert_eq!(value["default_value"], json!(6));
+        assert_eq!(
+            serde_json::from_value::<ConfigParameter>(value).unwrap(),
+            parameter
+        );
+    }
+
+    #[test]
+    fn test_error_json() {
+        let errors = [
+            TessError::CancelledError,
+            TessError::TimeoutError(Duration::from_secs(30)),
+            TessError::ExitCodeError {
+                code: 1,
+                stderr: "Error opening data file".into(),
+            },
+            TessError::UnsupportedVersionError {
+                feature: "PAGE XML".into(),
+                required: TesseractVersion::new(5, 3, 0),
+                found: TesseractVersion::new(4, 1, 1),
+            },
+        ];
+        let values = errors
+            .iter()
+            .map(|x| serde_json::to_value(x).unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            values,
+            vec![
+                json!({"kind": "CancelledError"}),
+                json!({"kind": "TimeoutError", "details": 30.0}),
+                json!({"kind": "ExitCodeError", "details": {"code": 1, "stderr": "Error opening data file"}}),
+                json!({"kind": "UnsupportedVersionError", "details": {"feature": "PAGE XML", "required": "5.3.0", "found": "4.1.1"}}),
+            ]
+        );
+        for (error, value) in errors.iter().zip(values) {
+            assert_eq!(&serde_json::from_value::<TessError>(value).unwrap(), error);
+        }
+
+        let spawn_error = serde_json::to_value(TessError::SpawnError(
+            std::io::Error::other("No such file or directory").into(),
+        ))
+        .unwrap();
+        assert_eq!(
+            spawn_error,
+            json!({"kind": "SpawnError", "details": "No such file or directory"})
+        );
+    }
+}
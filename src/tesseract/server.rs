@@ -0,0 +1,603 @@
+use super::*;
+use axum::{
+    body::Bytes,
+    extract::{multipart::Multipart, DefaultBodyLimit, FromRequest, Query, Request, State},
+    http::{header, StatusCode},
+    response::{IntoResponse, Response},
+    routing::{get, post},
+    Json, Router,
+};
+use serde::Serialize;
+use serde_json::json;
+use std::{future::Future, sync::Arc, time::Duration};
+use tokio::sync::{Semaphore, SemaphorePermit};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ServerOptions {
+    /// Number of tesseract processes that may run at the same time. Further
+    /// requests wait for a free slot.
+    pub max_processes: usize,
+    /// Largest accepted request body in bytes, larger uploads are answered
+    /// with `413 Payload Too Large`.
+    pub max_request_size: usize,
+    /// How long `/health` waits for a process slot and for tesseract before
+    /// it answers `503 Service Unavailable`.
+    pub health_timeout: Duration,
+}
+
+impl Default for ServerOptions {
+    fn default() -> Self {
+        ServerOptions {
+            max_processes: std::thread::available_parallelism().map_or(1, |x| x.get()),
+            max_request_size: 20 * 1024 * 1024,
+            health_timeout: Duration::from_secs(10),
+        }
+    }
+}
+
+#[derive(Debug)]
+struct ServerState {
+    tesseract: Tesseract,
+    processes: Semaphore,
+    health_timeout: Duration,
+}
+
+impl ServerState {
+    /// Waits for a free process slot.
+    async fn permit(&self) -> TessResult<SemaphorePermit<'_>> {
+        self.processes
+            .acquire()
+            .await
+            .map_err(|_| TessError::CancelledError)
+    }
+}
+
+type SharedState = Arc<ServerState>;
+
+impl Tesseract {
+    /// HTTP endpoints answering with JSON:
+    ///
+    /// - `POST /string`, `/data`, `/boxes` and `/osd` recognize the image in
+    ///   the request body, sent either as raw bytes or as the `image` field of
+    ///   a multipart form.
+    /// - `GET /version` and `/langs` describe the tesseract installation.
+    /// - `GET /health` runs tesseract and answers `200 OK` if it works.
+    ///
+    /// [`Args`] are read from the query string, e.g.
+    /// `?lang=deu&psm=6&timeout=30&config=tessedit_char_whitelist=0123456789`,
+    /// or from an `args` field with a JSON object in the multipart form, but
+    /// not both.
+    /// Failed requests answer with `{"error": ...}`, the serialized
+    /// [`TessError`]. A request whose client disconnects kills its tesseract
+    /// process.
+    pub fn server_router(&self, options: ServerOptions) -> Router {
+        let state = Arc::new(ServerState {
+            tesseract: self.clone(),
+            processes: Semaphore::new(options.max_processes.max(1)),
+            health_timeout: options.health_timeout,
+        });
+
+        Router::new()
+            .route("/string", post(string))
+            .route("/data", post(data))
+            .route("/boxes", post(boxes))
+            .route("/osd", post(osd))
+            .route("/version", get(version))
+            .route("/langs", get(langs))
+            .route("/health", get(health))
+            .layer(DefaultBodyLimit::max(options.max_request_size))
+            .with_state(state)
+    }
+}
+
+pub fn server_router(options: ServerOptions) -> Router {
+    Tesseract::shared().server_router(options)
+}
+
+/// Failed request, answered with its status and the serialized error.
+#[derive(Debug)]
+struct ApiError {
+    status: StatusCode,
+    error: TessError,
+}
+
+impl From<TessError> for ApiError {
+    fn from(error: TessError) -> Self {
+        ApiError {
+            status: status_code(&error),
+            error,
+        }
+    }
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        (self.status, Json(json!({"error": self.error}))).into_response()
+    }
+}
+
+/// Rejections of the body extractors, e.g. for a body over the size limit.
+fn rejection(status: StatusCode, message: String) -> ApiError {
+    ApiError {
+        status,
+        error: TessError::ArgumentError(message),
+    }
+}
+
+fn status_code(error: &TessError) -> StatusCode {
+    match error {
+        TessError::ArgumentError(_)
+        | TessError::ImageNotFoundError
+        | TessError::ImageReadError(_)
+        | TessError::DynamicImageError(_) => StatusCode::BAD_REQUEST,
+        TessError::ImageFormatError => StatusCode::UNSUPPORTED_MEDIA_TYPE,
+        TessError::MissingTraineddataError(_) | TessError::UnsupportedVersionError { .. } => {
+            StatusCode::UNPROCESSABLE_ENTITY
+        }
+        TessError::TesseractNotFoundError
+        | TessError::SpawnError(_)
+        | TessError::VersionError(_)
+        | TessError::CancelledError => StatusCode::SERVICE_UNAVAILABLE,
+        TessError::TimeoutError(_) => StatusCode::GATEWAY_TIMEOUT,
+        TessError::ProcessIoError(_)
+        | TessError::ExitCodeError { .. }
+        | TessError::SignalError { .. }
+        | TessError::OutputEncodingError(_)
+        | TessError::ParseError(_)
+        | TessError::SchemaError(_)
+        | TessError::LineParseError { .. }
+        | TessError::TempfileError(_)
+        | TessError::WatchError(_) => StatusCode::INTERNAL_SERVER_ERROR,
+    }
+}
+
+async fn string(
+    state: State<SharedState>,
+    query: Query<Vec<(String, String)>>,
+    request: Request,
+) -> Result<Json<StringOutput>, ApiError> {
+    recognize(state, query, request, |state, image, args| async move {
+        state.tesseract.image_to_string_async(&image, &args).await
+    })
+    .await
+}
+
+async fn data(
+    state: State<SharedState>,
+    query: Query<Vec<(String, String)>>,
+    request: Request,
+) -> Result<Json<DataOutput>, ApiError> {
+    recognize(state, query, request, |state, image, args| async move {
+        state.tesseract.image_to_data_async(&image, &args).await
+    })
+    .await
+}
+
+async fn boxes(
+    state: State<SharedState>,
+    query: Query<Vec<(String, String)>>,
+    request: Request,
+) -> Result<Json<BoxOutput>, ApiError> {
+    recognize(state, query, request, |state, image, args| async move {
+        state.tesseract.image_to_boxes_async(&image, &args).await
+    })
+    .await
+}
+
+async fn osd(
+    state: State<SharedState>,
+    query: Query<Vec<(String, String)>>,
+    request: Request,
+) -> Result<Json<OsdOutput>, ApiError> {
+    recognize(state, query, request, |state, image, args| async move {
+        state.tesseract.image_to_osd_async(&image, &args).await
+    })
+    .await
+}
+
+/// Reads the image and its arguments, then runs `run` once a process slot
+/// is free.
+async fn recognize<T, F, Fut>(
+    State(state): State<SharedState>,
+    Query(query): Query<Vec<(String, String)>>,
+    request: Request,
+    run: F,
+) -> Result<Json<T>, ApiError>
+where
+    T: Serialize,
+    F: FnOnce(SharedState, Image, Args) -> Fut,
+    Fut: Future<Output = TessResult<T>>,
+{
+    let has_query = !query.is_empty();
+    let mut args = args_from_query(query)?;
+    let is_multipart = request
+        .headers()
+        .get(header::CONTENT_TYPE)
+        .and_then(|x| x.to_str().ok())
+        .is_some_and(|x| x.starts_with("multipart/form-data"));
+
+    let data = match is_multipart {
+        true => {
+            let (data, form_args) = read_form(request).await?;
+            match form_args {
+                Some(_) if has_query => {
+                    return Err(TessError::ArgumentError(
+                        "args given both in the query and in the form".into(),
+                    )
+                    .into())
+                }
+                Some(form_args) => args = form_args,
+                None => {}
+            }
+            data
+        }
+        false => Bytes::from_request(request, &())
+            .await
+            .map_err(|e| rejection(e.status(), e.body_text()))?,
+    };
+    if data.is_empty() {
+        return Err(TessError::ArgumentError("request contains no image".into()).into());
+    }
+    args.validate()?;
+    let image = Image::from_bytes(data)?;
+
+    let _permit = state.permit().await?;
+    Ok(Json(run(state.clone(), image, args).await?))
+}
+
+/// The `image` field and the optional `args` field of a multipart form.
+async fn read_form(request: Request) -> Result<(Bytes, Option<Args>), ApiError> {
+    let mut form = Multipart::from_request(request, &())
+        .await
+        .map_err(|e| rejection(e.status(), e.body_text()))?;
+
+    let mut image = None;
+    let mut args = None;
+    while let Some(field) = form
+        .next_field()
+        .await
+        .map_err(|e| rejection(e.status(), e.body_text()))?
+    {
+        let name = field.name().unwrap_or_default().to_string();
+        let value = field
+            .bytes()
+            .await
+            .map_err(|e| rejection(e.status(), e.body_text()))?;
+        match name.as_str() {
+            "image" if image.is_some() => {
+                return Err(TessError::ArgumentError("duplicate image field".into()).into())
+            }
+            "image" => image = Some(value),
+            "args" => {
+                args =
+                    Some(serde_json::from_slice(&value).map_err(|e| {
+                        TessError::ArgumentError(format!("invalid args field: {}", e))
+                    })?)
+            }
+            _ => {}
+        }
+    }
+
+    let image = image.ok_or_else(|| TessError::ArgumentError("form has no image field".into()))?;
+    Ok((image, args))
+}
+
+/// Builds [`Args`] from query parameters named after its fields. `config`
+/// takes `NAME=VALUE` and may be repeated.
+fn args_from_query(query: Vec<(String, String)>) -> TessResult<Args> {
+    let invalid = |name: &str, value: &str| {
+        TessError::ArgumentError(format!("invalid value '{}' for '{}'", value, name))
+    };
+
+    let mut args = Args::default();
+    for (name, value) in query {
+        match name.as_str() {
+            "lang" => args.lang = value,
+            "config" => {
+                let (name, value) = value
+                    .split_once('=')
+                    .ok_or_else(|| invalid("config", &value))?;
+                // kept verbatim, e.g. a whitelist of `0123456789`
+                args.config_variables
+                    .insert(name.into(), ConfigValue::from(value));
+            }
+            "dpi" => args.dpi = value.parse().map_err(|_| invalid(&name, &value))?,
+            "psm" => args.psm = value.parse()?,
+            "oem" => args.oem = value.parse()?,
+            "timeout" => {
+                let seconds = value
+                    .parse()
+                    .ok()
+                    .and_then(|x| Duration::try_from_secs_f64(x).ok())
+                    .ok_or_else(|| invalid(&name, &value))?;
+                args.timeout = Some(seconds);
+            }
+            "auto_rotate" => {
+                args.auto_rotate = value.parse().map_err(|_| invalid(&name, &value))?
+            }
+            _ => {
+                return Err(TessError::ArgumentError(format!(
+                    "unknown parameter '{}'",
+                    name
+                )))
+            }
+        }
+    }
+    Ok(args)
+}
+
+/// Takes a process slot unless the version is cached.
+async fn version(State(state): State<SharedState>) -> Result<Response, ApiError> {
+    let _permit = match state.tesseract.version.get() {
+        Some(_) => None,
+        None => Some(state.permit().await?),
+    };
+    let version = state.tesseract.get_tesseract_version_async().await?;
+    let number = TesseractVersion::from_version_output(&version)?;
+    Ok(Json(json!({"version": version, "number": number})).into_response())
+}
+
+/// Takes a process slot unless the languages are cached.
+async fn langs(State(state): State<SharedState>) -> Result<Response, ApiError> {
+    let _permit = match state.tesseract.langs.get() {
+        Some(_) => None,
+        None => Some(state.permit().await?),
+    };
+    let langs = state.tesseract.get_tesseract_langs_async().await?;
+    Ok(Json(langs).into_response())
+}
+
+/// Lists the languages on every check, in a process slot like any other
+/// request. A fresh [`Tesseract`] bypasses the cached languages, so a
+/// tesseract that went missing is noticed, while the version is cached after
+/// the first check.
+async fn health(State(state): State<SharedState>) -> Response {
+    let tesseract = Tesseract::new(state.tesseract.executable());
+    let check = async {
+        let _permit = state.permit().await?;
+        let langs = tesseract.get_tesseract_langs_async().await?;
+        let version = state.tesseract.get_tesseract_version_number_async().await?;
+        TessResult::Ok((version, langs))
+    };
+    let result = tokio::time::timeout(state.health_timeout, check)
+        .await
+        .unwrap_or(Err(TessError::TimeoutError(state.health_timeout)));
+    match result {
+        Ok((version, langs)) => Json(json!({
+            "status": "ok",
+            "version": version,
+            "langs": langs,
+        }))
+        .into_response(),
+        Err(error) => (
+            StatusCode::SERVICE_UNAVAILABLE,
+            Json(json!({"status": "unavailable", "error": error})),
+        )
+            .into_response(),
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use crate::tesseract::test_util::FakeTesseract;
+    use crate::*;
+    use axum::{
+        body::Body,
+        http::{header, Request, StatusCode},
+        Router,
+    };
+    use http_body_util::BodyExt;
+    use serde_json::{json, Value};
+    use std::time::{Duration, Instant};
+    use tower::ServiceExt;
+
+    fn fake_tesseract() -> FakeTesseract {
+        FakeTesseract::new(
+            r#"case "$*" in
+                *--version*) echo 'tesseract 5.3.0' ;;
+                *--list-langs*) printf 'List of available languages (2):\neng\nosd\n' ;;
+                *"--psm 7"*) echo "$*" ;;
+                *) sleep 0.2; echo HELLO ;;
+            esac"#,
+        )
+    }
+
+    async fn send(router: &Router, request: Request<Body>) -> (StatusCode, Value) {
+        let response = router.clone().oneshot(request).await.unwrap();
+        let status = response.status();
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        (status, serde_json::from_slice(&body).unwrap())
+    }
+
+    fn post(uri: &str, body: impl Into<Body>) -> Request<Body> {
+        Request::post(uri).body(body.into()).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_server_recognize() {
+        let fake = fake_tesseract();
+        let router = fake.tesseract.server_router(ServerOptions::default());
+        let image = std::fs::read("img/string.png").unwrap();
+
+        let (status, value) = send(&router, post("/string", image.clone())).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(value["output"], json!("HELLO\n"));
+
+        // the fake echoes its arguments for a single line
+        let (status, value) = send(
+            &router,
+            post(
+                "/string?psm=single_line&lang=deu&config=tessedit_char_whitelist=0123456789",
+                image.clone(),
+            ),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        let output = value["output"].as_str().unwrap();
+        assert!(output.contains("-l deu"));
+        assert!(output.contains("-c tessedit_char_whitelist=0123456789"));
+
+        let boundary = "rusty-tesseract";
+        let mut form = format!(
+            "--{boundary}\r\nContent-Disposition: form-data; name=\"args\"\r\n\r\n\
+            {{\"psm\": 7, \"lang\": \"fra\"}}\r\n\
+            --{boundary}\r\nContent-Disposition: form-data; name=\"image\"; filename=\"string.png\"\r\n\
+            Content-Type: image/png\r\n\r\n"
+        )
+        .into_bytes();
+        form.extend_from_slice(&image);
+        form.extend_from_slice(format!("\r\n--{boundary}--\r\n").as_bytes());
+        let request = Request::post("/string")
+            .header(
+                header::CONTENT_TYPE,
+                format!("multipart/form-data; boundary={boundary}"),
+            )
+            .body(Body::from(form.clone()))
+            .unwrap();
+        let (status, value) = send(&router, request).await;
+        assert_eq!(status, StatusCode::OK);
+        assert!(value["output"].as_str().unwrap().contains("-l fra"));
+
+        let request = Request::post("/string?lang=deu")
+            .header(
+                header::CONTENT_TYPE,
+                format!("multipart/form-data; boundary={boundary}"),
+            )
+            .body(Body::from(form.clone()))
+            .unwrap();
+        let (status, value) = send(&router, request).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(value["error"]["kind"], json!("ArgumentError"));
+
+        // a second image field is rejected rather than silently replacing the first
+        let mut duplicate = form[..form.len() - format!("--{boundary}--\r\n").len()].to_vec();
+        duplicate.extend_from_slice(
+            format!(
+                "--{boundary}\r\nContent-Disposition: form-data; name=\"image\"\r\n\r\n\
+                other\r\n--{boundary}--\r\n"
+            )
+            .as_bytes(),
+        );
+        let request = Request::post("/string")
+            .header(
+                header::CONTENT_TYPE,
+                format!("multipart/form-data; boundary={boundary}"),
+            )
+            .body(Body::from(duplicate))
+            .unwrap();
+        let (status, value) = send(&router, request).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(
+            value["error"],
+            json!({"kind": "ArgumentError", "details": "duplicate image field"})
+        );
+    }
+
+    #[tokio::test]
+    async fn test_server_errors() {
+        let fake = fake_tesseract();
+        let router = fake.tesseract.server_router(ServerOptions {
+            max_request_size: 1024 * 1024,
+            ..Default::default()
+        });
+        let image = std::fs::read("img/string.png").unwrap();
+
+        let (status, value) = send(&router, post("/data?sideways=1", image.clone())).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(value["error"]["kind"], json!("ArgumentError"));
+
+        let (status, _) = send(&router, post("/data?psm=14", image.clone())).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+
+        let (status, value) = send(&router, post("/data?psm=sideways", image)).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(value["error"]["kind"], json!("ArgumentError"));
+
+        let (status, value) = send(&router, post("/boxes", "not an image")).await;
+        assert_eq!(status, StatusCode::UNSUPPORTED_MEDIA_TYPE);
+        assert_eq!(value["error"]["kind"], json!("ImageFormatError"));
+
+        let (status, _) = send(&router, post("/osd", vec![0; 2 * 1024 * 1024])).await;
+        assert_eq!(status, StatusCode::PAYLOAD_TOO_LARGE);
+
+        let missing = Tesseract::new("/nonexistent/tesseract").server_router(Default::default());
+        let request = Request::get("/health").body(Body::empty()).unwrap();
+        let (status, value) = send(&missing, request).await;
+        assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(value["error"]["kind"], json!("TesseractNotFoundError"));
+    }
+
+    #[tokio::test]
+    async fn test_server_info() {
+        let fake = fake_tesseract();
+        let router = fake.tesseract.server_router(ServerOptions::default());
+        let get = |uri| Request::get(uri).body(Body::empty()).unwrap();
+
+        let (status, value) = send(&router, get("/health")).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(
+            value,
+            json!({"status": "ok", "version": "5.3.0", "langs": ["eng", "osd"]})
+        );
+        let (_, value) = send(&router, get("/version")).await;
+        assert_eq!(value["number"], json!("5.3.0"));
+        let (_, value) = send(&router, get("/langs")).await;
+        assert_eq!(value, json!(["eng", "osd"]));
+
+        // the health check does not rely on the cached version
+        std::fs::remove_file(fake.tesseract.executable()).unwrap();
+        let (status, _) = send(&router, get("/health")).await;
+        assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+        let (status, _) = send(&router, get("/version")).await;
+        assert_eq!(status, StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn test_server_process_limit() {
+        let fake = fake_tesseract();
+        let router = fake.tesseract.server_router(ServerOptions {
+            max_processes: 1,
+            ..Default::default()
+        });
+        let image = std::fs::read("img/string.png").unwrap();
+
+        let start = Instant::now();
+        let (first, second) = tokio::join!(
+            send(&router, post("/string", image.clone())),
+            send(&router, post("/string", image)),
+        );
+        assert_eq!(first.0, StatusCode::OK);
+        assert_eq!(second.0, StatusCode::OK);
+        // each request sleeps for 0.2s, one after the other
+        assert!(start.elapsed() >= Duration::from_millis(400));
+    }
+
+    #[tokio::test]
+    async fn test_server_health_limit() {
+        let fake = FakeTesseract::new(
+            r#"case "$*" in
+                *--version*) echo 'tesseract 5.3.0' ;;
+                *--list-langs*) sleep 0.4; printf 'List of available languages (1):\neng\n' ;;
+            esac"#,
+        );
+        let router = fake.tesseract.server_router(ServerOptions {
+            max_processes: 1,
+            health_timeout: Duration::from_millis(600),
+            ..Default::default()
+        });
+        let get = || Request::get("/health").body(Body::empty()).unwrap();
+
+        // the second check waits for the first one's process slot and runs out
+        // of time
+        let (first, second) = tokio::join!(send(&router, get()), send(&router, get()));
+        let mut statuses = [first.0, second.0];
+        statuses.sort();
+        assert_eq!(statuses, [StatusCode::OK, StatusCode::SERVICE_UNAVAILABLE]);
+        let failed = if first.0 == StatusCode::OK {
+            second.1
+        } else {
+            first.1
+        };
+        assert_eq!(failed["error"]["kind"], json!("TimeoutError"));
+    }
+}
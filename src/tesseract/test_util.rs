@@ -0,0 +1,27 @@
+#[cfg(unix)]
+use crate::Tesseract;
+use crate::{BoxOutput, DataOutput, ProcessOutput};
+#[cfg(unix)]
+mod fake_tesseract;
+#[cfg(unix)]
+pub(crate) use fake_tesseract::FakeTesseract;
+
+/// Parses TSV rows as printed by tesseract after the header line.
+pub(crate) fn data_output(rows: &str) -> DataOutput {
+    DataOutput::from_output(ProcessOutput {
+        stdout: format!(
+            "level\tpage_num\tblock_num\tpar_num\tline_num\tword_num\tleft\ttop\twidth\theight\tconf\ttext\n{}",
+            rows
+        ),
+        diagnostics: vec![],
+    })
+    .unwrap()
+}
+
+pub(crate) fn box_output(stdout: &str) -> BoxOutput {
+    BoxOutput::from_output(ProcessOutput {
+        stdout: stdout.into(),
+        diagnostics: vec![],
+    })
+    .unwrap()
+}
@@ -0,0 +1,38 @@
+use super::Tesseract;
+use std::{fs::OpenOptions, io::Write, os::unix::fs::OpenOptionsExt, sync::Mutex};
+use tempfile::TempDir;
+
+/// Held while a script is written. Test threads that fork in the meantime
+/// inherit the open script, and running it fails with ETXTBSY until they
+/// exec.
+static WRITING: Mutex<()> = Mutex::new(());
+
+/// Shell script standing in for the tesseract executable.
+pub(crate) struct FakeTesseract {
+    pub tesseract: Tesseract,
+    _dir: TempDir,
+}
+
+impl FakeTesseract {
+    pub fn new(script: &str) -> Self {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("tesseract");
+        {
+            let _writing = WRITING.lock().unwrap_or_else(|e| e.into_inner());
+            let mut file = OpenOptions::new()
+                .write(true)
+                .create_new(true)
+                .mode(0o755)
+                .open(&path)
+                .unwrap();
+            file.write_all(format!("#!/bin/sh\n{}\n", script).as_bytes())
+                .unwrap();
+            file.sync_all().unwrap();
+        }
+
+        Self {
+            tesseract: Tesseract::new(path),
+            _dir: dir,
+        }
+    }
+}
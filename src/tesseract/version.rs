@@ -0,0 +1,124 @@
+use super::*;
+use core::fmt;
+use std::str::FromStr;
+
+/// Numeric tesseract version, used to gate features of newer releases.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct TesseractVersion {
+    pub major: u32,
+    pub minor: u32,
+    pub patch: u32,
+}
+
+impl TesseractVersion {
+    pub const fn new(major: u32, minor: u32, patch: u32) -> Self {
+        TesseractVersion {
+            major,
+            minor,
+            patch,
+        }
+    }
+
+    /// Reads the version from the first line of `tesseract --version`, e.g.
+    /// `tesseract 5.3.0` or `tesseract v5.0.0-alpha.20201127`.
+    pub fn from_version_output(output: &str) -> TessResult<Self> {
+        output
+            .lines()
+            .next()
+            .and_then(|line| line.split_whitespace().nth(1))
+            .ok_or_else(|| TessError::VersionError(output.into()))?
+            .parse()
+    }
+
+    pub(crate) fn require(&self, required: TesseractVersion, feature: &str) -> TessResult<()> {
+        if *self < required {
+            return Err(TessError::UnsupportedVersionError {
+                feature: feature.into(),
+                required,
+                found: *self,
+            });
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for TesseractVersion {
+    type Err = TessError;
+
+    /// Parses `5.3.0`, ignoring a leading `v` and suffixes like `-rc2`.
+    /// Missing components default to 0.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let version = s.strip_prefix('v').unwrap_or(s);
+        let end = version
+            .find(|c: char| !c.is_ascii_digit() && c != '.')
+            .unwrap_or(version.len());
+
+        let mut parts = version[..end].split('.').map(|x| x.parse::<u32>().ok());
+        let major = parts.next().flatten();
+        let minor = parts.next().unwrap_or(Some(0));
+        let patch = parts.next().unwrap_or(Some(0));
+
+        match (major, minor, patch) {
+            (Some(major), Some(minor), Some(patch)) => Ok(Self::new(major, minor, patch)),
+            _ => Err(TessError::VersionError(s.into())),
+        }
+    }
+}
+
+impl fmt::Display for TesseractVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}.{}", self.major, self.minor, self.patch)
+    }
+}
+
+impl Tesseract {
+    pub fn get_tesseract_version_number(&self) -> TessResult<TesseractVersion> {
+        TesseractVersion::from_version_output(&self.get_tesseract_version()?)
+    }
+}
+
+pub fn get_tesseract_version_number() -> TessResult<TesseractVersion> {
+    Tesseract::shared().get_tesseract_version_number()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    #[test]
+    fn test_parse_version() {
+        let output = "tesseract 5.3.0\n leptonica-1.82.0\n  libgif 5.2.1 : libjpeg 8d\n";
+        assert_eq!(
+            TesseractVersion::from_version_output(output),
+            Ok(TesseractVersion::new(5, 3, 0))
+        );
+        assert_eq!(
+            TesseractVersion::from_version_output("tesseract v5.0.0-alpha.20201127\n"),
+            Ok(TesseractVersion::new(5, 0, 0))
+        );
+        assert_eq!(
+            "3.05".parse::<TesseractVersion>(),
+            Ok(TesseractVersion::new(3, 5, 0))
+        );
+        assert!(matches!(
+            TesseractVersion::from_version_output("tesseract\n"),
+            Err(TessError::VersionError(_))
+        ));
+    }
+
+    #[test]
+    fn test_require_version() {
+        let version = TesseractVersion::new(5, 2, 0);
+        assert!(version
+            .require(TesseractVersion::new(4, 1, 0), "alto")
+            .is_ok());
+        assert_eq!(
+            version.require(TesseractVersion::new(5, 3, 0), "PAGE XML"),
+            Err(TessError::UnsupportedVersionError {
+                feature: "PAGE XML".into(),
+                required: TesseractVersion::new(5, 3, 0),
+                found: version,
+            })
+        );
+    }
+}
@@ -0,0 +1,549 @@
+use super::*;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{BTreeMap, HashMap},
+    fs, io,
+    io::Write,
+    path::{Path, PathBuf},
+    str::FromStr,
+    thread,
+    time::{Duration, Instant, SystemTime},
+};
+use tempfile::NamedTempFile;
+
+/// Name of the record of processed files in the watched directory.
+pub const STATE_FILE_NAME: &str = ".rusty-tesseract-watch.json";
+
+/// File written next to each recognized image, named after the image with
+/// the format's extension appended, e.g. `scan.png.txt`, so that `scan.png`
+/// and `scan.jpg` do not share a sidecar.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SidecarFormat {
+    /// `.txt` with the recognized text.
+    Text,
+    /// `.tsv` with tesseract's TSV data.
+    Tsv,
+    /// `.json` with the serialized [`DataOutput`].
+    Json,
+}
+
+impl SidecarFormat {
+    pub fn extension(self) -> &'static str {
+        match self {
+            SidecarFormat::Text => "txt",
+            SidecarFormat::Tsv => "tsv",
+            SidecarFormat::Json => "json",
+        }
+    }
+}
+
+impl FromStr for SidecarFormat {
+    type Err = TessError;
+
+    /// Accepts the extension or the variant name, e.g. `txt` or `text`.
+    fn from_str(s: &str) -> TessResult<Self> {
+        match s.trim().to_lowercase().as_str() {
+            "txt" | "text" => Ok(SidecarFormat::Text),
+            "tsv" => Ok(SidecarFormat::Tsv),
+            "json" => Ok(SidecarFormat::Json),
+            _ => Err(TessError::ArgumentError(format!(
+                "unknown sidecar format '{}'",
+                s
+            ))),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct WatchOptions {
+    pub args: Args,
+    pub sidecars: Vec<SidecarFormat>,
+    /// Time between two scans of the directory.
+    pub poll_interval: Duration,
+    /// How long the size and modification time of a file must stay the same
+    /// before it is recognized, so files that are still being written are
+    /// left alone.
+    pub settle_time: Duration,
+    /// Record of processed files, [`STATE_FILE_NAME`] in the watched
+    /// directory when unset.
+    pub state_file: Option<PathBuf>,
+}
+
+impl Default for WatchOptions {
+    fn default() -> Self {
+        WatchOptions {
+            args: Args::default(),
+            sidecars: vec![SidecarFormat::Text],
+            poll_interval: Duration::from_secs(1),
+            settle_time: Duration::from_secs(2),
+            state_file: None,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum WatchEvent {
+    Processed {
+        image: PathBuf,
+        sidecars: Vec<PathBuf>,
+    },
+    /// Failures caused by the image itself are not retried until it
+    /// changes. Transient and environment failures (see
+    /// [`TessError::is_transient`] and [`TessError::is_environment_error`]),
+    /// like a timeout, a missing tesseract or language, or a sidecar that
+    /// could not be written, are retried once the file settles again.
+    Failed { image: PathBuf, error: TessError },
+}
+
+/// Recognizes the images that appear in a directory and writes sidecar files
+/// next to them.
+///
+/// Only the top level of the directory is watched. Hidden files and files
+/// without an image extension are ignored. Processed images are recorded
+/// with their size and modification time, so a restarted watcher skips them
+/// unless they changed in the meantime.
+#[derive(Debug)]
+pub struct DirectoryWatcher {
+    tesseract: Tesseract,
+    dir: PathBuf,
+    options: WatchOptions,
+    state_file: PathBuf,
+    state: State,
+    /// Files waiting for their size and modification time to settle.
+    pending: HashMap<PathBuf, Pending>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct State {
+    /// Processed files by file name.
+    files: BTreeMap<String, FileVersion>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+struct FileVersion {
+    len: u64,
+    modified: SystemTime,
+    /// Error message if recognizing this version of the file failed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+}
+
+#[derive(Debug)]
+struct Pending {
+    len: u64,
+    modified: SystemTime,
+    since: Instant,
+}
+
+impl DirectoryWatcher {
+    pub fn new<P: Into<PathBuf>>(
+        tesseract: Tesseract,
+        dir: P,
+        options: WatchOptions,
+    ) -> TessResult<Self> {
+        options.args.validate()?;
+        if options.sidecars.is_empty() {
+            return Err(TessError::ArgumentError("no sidecar formats".into()));
+        }
+        let dir = dir.into();
+        if !dir.is_dir() {
+            return Err(TessError::WatchError(
+                io::Error::new(
+                    io::ErrorKind::NotFound,
+                    format!("{} is not a directory", dir.display()),
+                )
+                .into(),
+            ));
+        }
+
+        let state_file = options
+            .state_file
+            .clone()
+            .unwrap_or_else(|| dir.join(STATE_FILE_NAME));
+        let state = match fs::read(&state_file) {
+            Ok(x) => serde_json::from_slice(&x).map_err(|e| watch_error(e.into()))?,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => State::default(),
+            Err(e) => return Err(watch_error(e)),
+        };
+
+        Ok(DirectoryWatcher {
+            tesseract,
+            dir,
+            options,
+            state_file,
+            state,
+            pending: HashMap::new(),
+        })
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Scans the directory once and recognizes the images that have settled
+    /// since they were first seen. The record of processed files is saved
+    /// after every image.
+    pub fn poll(&mut self) -> TessResult<Vec<WatchEvent>> {
+        let now = Instant::now();
+        let mut events = Vec::new();
+        let candidates = self.candidates()?;
+
+        for (path, name, len, modified) in &candidates {
+            let (len, modified) = (*len, *modified);
+            let current = |x: &FileVersion| x.len == len && x.modified == modified;
+            if self.state.files.get(name).is_some_and(current) {
+                continue;
+            }
+            match self.pending.get(path) {
+                Some(x) if x.len == len && x.modified == modified => {
+                    if len == 0 || now.duration_since(x.since) < self.options.settle_time {
+                        continue;
+                    }
+                }
+                _ => {
+                    self.pending.insert(
+                        path.clone(),
+                        Pending {
+                            len,
+                            modified,
+                            since: now,
+                        },
+                    );
+                    continue;
+                }
+            }
+
+            self.pending.remove(path);
+            let event = match self.recognize(path) {
+                Ok(sidecars) => WatchEvent::Processed {
+                    image: path.clone(),
+                    sidecars,
+                },
+                Err(error) => WatchEvent::Failed {
+                    image: path.clone(),
+                    error,
+                },
+            };
+            if matches!(&event, WatchEvent::Failed { error, .. } if is_retried(error)) {
+                events.push(event);
+                continue;
+            }
+            let error = match &event {
+                WatchEvent::Failed { error, .. } => Some(error.to_string()),
+                WatchEvent::Processed { .. } => None,
+            };
+            self.state.files.insert(
+                name.clone(),
+                FileVersion {
+                    len,
+                    modified,
+                    error,
+                },
+            );
+            self.save_state()?;
+            events.push(event);
+        }
+
+        // forget files deleted before they settled
+        self.pending
+            .retain(|path, _| candidates.iter().any(|x| &x.0 == path));
+        Ok(events)
+    }
+
+    /// Polls the directory until `cancellation` is cancelled and hands every
+    /// event to `on_event`.
+    pub fn run(
+        &mut self,
+        cancellation: &CancellationToken,
+        mut on_event: impl FnMut(WatchEvent),
+    ) -> TessResult<()> {
+        while !cancellation.is_cancelled() {
+            self.poll()?.into_iter().for_each(&mut on_event);
+            thread::sleep(self.options.poll_interval);
+        }
+        Ok(())
+    }
+
+    /// Image files in the directory with their name, size and modification
+    /// time, ordered by name.
+    fn candidates(&self) -> TessResult<Vec<(PathBuf, String, u64, SystemTime)>> {
+        let mut candidates = Vec::new();
+        for entry in fs::read_dir(&self.dir).map_err(watch_error)? {
+            let entry = entry.map_err(watch_error)?;
+            let Ok(name) = entry.file_name().into_string() else {
+                continue;
+            };
+            let path = entry.path();
+            let is_image = path
+                .extension()
+                .and_then(|x| x.to_str())
+                .and_then(InputFormat::from_extension)
+                .is_some();
+            if name.starts_with('.') || !is_image || path == self.state_file {
+                continue;
+            }
+            // the file may be gone already
+            let Ok(metadata) = entry.metadata() else {
+                continue;
+            };
+            if metadata.is_file() {
+                let Ok(modified) = metadata.modified() else {
+                    continue;
+                };
+                candidates.push((path, name, metadata.len(), modified));
+            }
+        }
+        candidates.sort_by(|a, b| a.1.cmp(&b.1));
+        Ok(candidates)
+    }
+
+    /// Runs tesseract once, for TSV data if any sidecar needs it and for
+    /// text otherwise, then writes the sidecars. Text next to TSV data is
+    /// taken from the data.
+    fn recognize(&self, path: &Path) -> TessResult<Vec<PathBuf>> {
+        let image = Image::from_path(path)?;
+        let args = &self.options.args;
+        let sidecars = &self.options.sidecars;
+
+        let (text, data) = match sidecars
+            .iter()
+            .any(|x| matches!(x, SidecarFormat::Tsv | SidecarFormat::Json))
+        {
+            true => {
+                let data = self.tesseract.image_to_data(&image, args)?;
+                let text = match sidecars.contains(&SidecarFormat::Text) {
+                    true => Some(data.to_document()?.to_text() + "\n"),
+                    false => None,
+                };
+                (text, Some(data))
+            }
+            false => {
+                let text = self.tesseract.image_to_string(&image, args)?;
+                (Some(text.output), None)
+            }
+        };
+
+        let mut written = Vec::new();
+        for format in sidecars {
+            let contents = match (format, &text, &data) {
+                (SidecarFormat::Text, Some(text), _) => text.clone().into_bytes(),
+                (SidecarFormat::Tsv, _, Some(data)) => data.output.clone().into_bytes(),
+                (SidecarFormat::Json, _, Some(data)) => {
+                    serde_json::to_vec_pretty(data).map_err(|e| watch_error(e.into()))?
+                }
+                _ => unreachable!("recognized above"),
+            };
+            let mut sidecar = path.as_os_str().to_owned();
+            sidecar.push(".");
+            sidecar.push(format.extension());
+            let sidecar = PathBuf::from(sidecar);
+            write_atomically(&sidecar, &contents)?;
+            written.push(sidecar);
+        }
+        Ok(written)
+    }
+
+    fn save_state(&self) -> TessResult<()> {
+        let contents = serde_json::to_vec_pretty(&self.state).map_err(|e| watch_error(e.into()))?;
+        write_atomically(&self.state_file, &contents)
+    }
+}
+
+/// Writes through a hidden temporary file in the same directory, so readers
+/// never see a partially written file.
+fn write_atomically(path: &Path, contents: &[u8]) -> TessResult<()> {
+    let dir = path.parent().unwrap_or(Path::new("."));
+    let mut file = NamedTempFile::new_in(dir).map_err(watch_error)?;
+    file.write_all(contents).map_err(watch_error)?;
+    file.persist(path).map_err(|e| watch_error(e.error))?;
+    Ok(())
+}
+
+/// Whether recognizing the image might succeed later although the file stays
+/// the same.
+fn is_retried(error: &TessError) -> bool {
+    error.is_transient()
+        || error.is_environment_error()
+        || matches!(error, TessError::CancelledError)
+}
+
+fn watch_error(error: io::Error) -> TessError {
+    TessError::WatchError(error.into())
+}
+
+impl Tesseract {
+    pub fn watch_directory<P: Into<PathBuf>>(
+        &self,
+        dir: P,
+        options: WatchOptions,
+    ) -> TessResult<DirectoryWatcher> {
+        DirectoryWatcher::new(self.clone(), dir, options)
+    }
+}
+
+pub fn watch_directory<P: Into<PathBuf>>(
+    dir: P,
+    options: WatchOptions,
+) -> TessResult<DirectoryWatcher> {
+    Tesseract::shared().watch_directory(dir, options)
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use crate::tesseract::test_util::FakeTesseract;
+    use crate::*;
+    use std::{fs, time::Duration};
+
+    fn fake_tesseract() -> FakeTesseract {
+        FakeTesseract::new(
+            r#"echo "$*" >> "$0.log"
+            case "$*" in
+                *tsv*) printf 'level\tpage_num\tblock_num\tpar_num\tline_num\tword_num\tleft\ttop\twidth\theight\tconf\ttext\n1\t1\t0\t0\t0\t0\t0\t0\t10\t10\t-1\t\n5\t1\t1\t1\t1\t1\t0\t0\t10\t10\t95\tHELLO\n' ;;
+                *) echo HELLO ;;
+            esac"#,
+        )
+    }
+
+    #[test]
+    fn test_directory_watcher() {
+        let fake = fake_tesseract();
+        let dir = tempfile::tempdir().unwrap();
+        let image = dir.path().join("scan.png");
+        fs::copy("img/string.png", &image).unwrap();
+        fs::write(dir.path().join("notes.md"), "not an image").unwrap();
+
+        let options = WatchOptions {
+            sidecars: vec![SidecarFormat::Text, SidecarFormat::Tsv, SidecarFormat::Json],
+            settle_time: Duration::ZERO,
+            ..Default::default()
+        };
+        let mut watcher = fake
+            .tesseract
+            .watch_directory(dir.path(), options.clone())
+            .unwrap();
+
+        // the first scan only notes the new file
+        assert_eq!(watcher.poll().unwrap(), vec![]);
+        assert_eq!(
+            watcher.poll().unwrap(),
+            vec![WatchEvent::Processed {
+                image: image.clone(),
+                sidecars: vec![
+                    dir.path().join("scan.png.txt"),
+                    dir.path().join("scan.png.tsv"),
+                    dir.path().join("scan.png.json"),
+                ],
+            }]
+        );
+        assert_eq!(
+            fs::read_to_string(dir.path().join("scan.png.txt")).unwrap(),
+            "HELLO\n"
+        );
+        assert!(fs::read_to_string(dir.path().join("scan.png.json"))
+            .unwrap()
+            .contains(r#""text": "HELLO""#));
+        // the text is taken from the TSV data
+        let log = fake.tesseract.executable().with_extension("log");
+        assert_eq!(fs::read_to_string(&log).unwrap().lines().count(), 1);
+        assert_eq!(watcher.poll().unwrap(), vec![]);
+
+        // a restarted watcher remembers the image
+        let mut watcher = fake.tesseract.watch_directory(dir.path(), options).unwrap();
+        assert_eq!(watcher.poll().unwrap(), vec![]);
+        assert_eq!(watcher.poll().unwrap(), vec![]);
+    }
+
+    #[test]
+    fn test_directory_watcher_waits_for_files_to_settle() {
+        let fake = fake_tesseract();
+        let dir = tempfile::tempdir().unwrap();
+        let image = dir.path().join("scan.png");
+        fs::write(&image, b"partial").unwrap();
+
+        let options = WatchOptions {
+            settle_time: Duration::from_secs(3600),
+            ..Default::default()
+        };
+        let mut watcher = fake.tesseract.watch_directory(dir.path(), options).unwrap();
+        assert_eq!(watcher.poll().unwrap(), vec![]);
+        fs::copy("img/string.png", &image).unwrap();
+        assert_eq!(watcher.poll().unwrap(), vec![]);
+        assert!(!dir.path().join("scan.png.txt").exists());
+    }
+
+    #[test]
+    fn test_directory_watcher_retries() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::copy("img/string.png", dir.path().join("scan.png")).unwrap();
+        fs::copy("img/string.png", dir.path().join("broken.png")).unwrap();
+        fs::copy("img/string.png", dir.path().join("french.png")).unwrap();
+
+        // tesseract rejects one image, lacks the language for another and is
+        // too slow for the last
+        let fake = FakeTesseract::new(
+            r#"case "$*" in
+                *broken*) echo 'Error in pixReadStream' >&2; exit 1 ;;
+                *french*) echo "Failed loading language 'fra'" >&2; exit 1 ;;
+                *) sleep 5 ;;
+            esac"#,
+        );
+        let options = WatchOptions {
+            args: Args {
+                timeout: Some(Duration::from_millis(200)),
+                ..Default::default()
+            },
+            settle_time: Duration::ZERO,
+            ..Default::default()
+        };
+        let mut watcher = fake.tesseract.watch_directory(dir.path(), options).unwrap();
+        let errors = |events: Vec<WatchEvent>| {
+            events
+                .into_iter()
+                .map(|x| match x {
+                    WatchEvent::Failed { image, error } => (image, std::mem::discriminant(&error)),
+                    x => panic!("unexpected {:?}", x),
+                })
+                .collect::<Vec<_>>()
+        };
+
+        let exit_code_error = std::mem::discriminant(&TessError::ExitCodeError {
+            code: 1,
+            stderr: String::new(),
+        });
+        let timeout_error = std::mem::discriminant(&TessError::TimeoutError(Duration::ZERO));
+
+        assert_eq!(watcher.poll().unwrap(), vec![]);
+        assert_eq!(
+            errors(watcher.poll().unwrap()),
+            vec![
+                (dir.path().join("broken.png"), exit_code_error),
+                (dir.path().join("french.png"), exit_code_error),
+                (dir.path().join("scan.png"), timeout_error),
+            ]
+        );
+        // only the broken image is not retried once the files settle again
+        assert_eq!(watcher.poll().unwrap(), vec![]);
+        assert_eq!(
+            errors(watcher.poll().unwrap()),
+            vec![
+                (dir.path().join("french.png"), exit_code_error),
+                (dir.path().join("scan.png"), timeout_error),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_directory_watcher_errors() {
+        assert_eq!(
+            "pdf".parse::<SidecarFormat>(),
+            Err(TessError::ArgumentError(
+                "unknown sidecar format 'pdf'".into()
+            ))
+        );
+        assert!(matches!(
+            watch_directory("img/string.png", WatchOptions::default()),
+            Err(TessError::WatchError(_))
+        ));
+    }
+}
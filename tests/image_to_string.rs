@@ -0,0 +1,33 @@
+use rusty_tesseract::{Args, Image, PageSegMode};
+
+#[test]
+fn vertical_text() {
+    let img = Image::from_path("img/vertical_text.png").unwrap();
+
+    let image_to_string_args = Args {
+        psm: PageSegMode::SingleBlock,
+        ..Default::default()
+    };
+
+    let output = rusty_tesseract::image_to_string(&img, &image_to_string_args).unwrap();
+    assert_eq!(
+        output.lines().collect::<Vec<&str>>(),
+        vec!["D", "O", "L", "O", "R", "S", "I", "", "T"]
+    );
+}
+
+#[test]
+fn horizontal_text() {
+    let img = Image::from_path("img/horizontal_text.png").unwrap();
+    let default_args = Args::default();
+    let output = rusty_tesseract::image_to_string(&img, &default_args).unwrap();
+    assert_eq!(output.trim(), "Lorem ipsum dolor sit amet");
+}
+
+#[test]
+fn image_to_string() {
+    let img = Image::from_path("img/string.png").unwrap();
+    let default_args = Args::default();
+    let output = rusty_tesseract::image_to_string(&img, &default_args).unwrap();
+    assert_eq!(output.trim(), "LOREM IPSUM DOLOR SIT AMET");
+}